@@ -0,0 +1,110 @@
+use regex::Regex;
+use serde::{Serialize, Deserialize};
+use simple_text_decode::DecodedText;
+use ts_rs::TS;
+
+lazy_static! {
+    static ref HEADER: Regex = Regex::new(r"^\[CUETools log; .*Version: (.+)\]").unwrap();
+    static ref CTDB_TOCID: Regex = Regex::new(r"^\[CTDB TOCID: (\S+)\]").unwrap();
+    static ref ACCURATERIP_ID: Regex = Regex::new(r"^\[AccurateRip ID: (\S+)\]").unwrap();
+    // "[f0c1d6ab] (170/174) Accurately ripped"
+    static ref CTDB_DISC: Regex = Regex::new(r"^\[[0-9A-Fa-f]{8}\]\s+\((\d+)/(\d+)\)\s+(.+)$").unwrap();
+    // "  1   | (170/174) Accurately ripped"
+    static ref CTDB_TRACK: Regex = Regex::new(r"^(\d+)\s+\|\s+\((\d+)/(\d+)\)\s+(.+)$").unwrap();
+    // " 01     [40bd6e8a|4f4b5dd0] (046/207) Accurately ripped"
+    static ref ACCURATERIP_TRACK: Regex = Regex::new(r"^(\d+)\s+\[[0-9A-Fa-f|]+\]\s+\((\d+)/(\d+)\)\s+(.+)$").unwrap();
+}
+
+#[derive(Serialize, Deserialize, TS, Debug, Clone, Copy, PartialEq, Eq)]
+#[ts(export)]
+pub struct AccuripConfidence {
+    pub matching: u32,
+    pub total: u32,
+    pub accurate: bool,
+}
+
+#[derive(Serialize, Deserialize, TS, Debug)]
+#[ts(export)]
+pub struct AccuripTrack {
+    pub num: u8,
+    pub accuraterip: Option<AccuripConfidence>,
+    pub ctdb: Option<AccuripConfidence>,
+}
+
+// Verification report CUETools writes next to the rip, holds AR/CTDB results without any of the extraction details
+#[derive(Serialize, Deserialize, TS, Debug, Default)]
+#[ts(export)]
+pub struct AccuripReport {
+    pub version: Option<String>,
+    pub ctdb_tocid: Option<String>,
+    pub ctdb: Option<AccuripConfidence>,
+    pub accuraterip_id: Option<String>,
+    pub tracks: Vec<AccuripTrack>,
+}
+
+impl AccuripConfidence {
+    fn from_captures(matching: &str, total: &str, status: &str) -> Option<Self> {
+        Some(Self {
+            matching: matching.parse().ok()?,
+            total: total.parse().ok()?,
+            accurate: status.starts_with("Accurately ripped"),
+        })
+    }
+
+    // Accurate results win over mismatches, then the higher confidence
+    fn best(current: Option<Self>, other: Self) -> Option<Self> {
+        match current {
+            Some(current) if (current.accurate, current.matching) >= (other.accurate, other.matching) => Some(current),
+            _ => Some(other),
+        }
+    }
+}
+
+impl AccuripReport {
+    pub fn from_text(text: &str) -> Self {
+        let mut report = Self::default();
+
+        for line in text.lines().map(str::trim) {
+            if let Some(caps) = HEADER.captures(line) {
+                report.version = Some(caps[1].to_owned());
+            } else if let Some(caps) = CTDB_TOCID.captures(line) {
+                report.ctdb_tocid = Some(caps[1].to_owned());
+            } else if let Some(caps) = ACCURATERIP_ID.captures(line) {
+                report.accuraterip_id = Some(caps[1].to_owned());
+            } else if let Some(caps) = CTDB_DISC.captures(line) {
+                if let Some(confidence) = AccuripConfidence::from_captures(&caps[1], &caps[2], &caps[3]) {
+                    report.ctdb = AccuripConfidence::best(report.ctdb, confidence);
+                }
+            } else if let Some(caps) = CTDB_TRACK.captures(line) {
+                if let Some(confidence) = AccuripConfidence::from_captures(&caps[2], &caps[3], &caps[4]) {
+                    let track = report.track_mut(&caps[1]);
+                    track.ctdb = AccuripConfidence::best(track.ctdb, confidence);
+                }
+            } else if let Some(caps) = ACCURATERIP_TRACK.captures(line) {
+                // Offsetted blocks repeat the tracks, the best result across offsets is kept
+                if let Some(confidence) = AccuripConfidence::from_captures(&caps[2], &caps[3], &caps[4]) {
+                    let track = report.track_mut(&caps[1]);
+                    track.accuraterip = AccuripConfidence::best(track.accuraterip, confidence);
+                }
+            }
+        }
+
+        report
+    }
+
+    pub fn from_bytes(raw: &[u8]) -> Self {
+        Self::from_text(&DecodedText::new(raw).unwrap_or_default().text)
+    }
+
+    fn track_mut(&mut self, num: &str) -> &mut AccuripTrack {
+        let num = num.parse::<u8>().unwrap_or_default();
+        let idx = match self.tracks.iter().position(|track| track.num == num) {
+            Some(idx) => idx,
+            None => {
+                self.tracks.push(AccuripTrack { num, accuraterip: None, ctdb: None });
+                self.tracks.len() - 1
+            },
+        };
+        &mut self.tracks[idx]
+    }
+}
@@ -0,0 +1,116 @@
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+
+use crate::parser::{ParsedLog, ParsedLogCombined};
+use crate::track::{TrackEntry, TrackError};
+
+// Drive strings reported by image mounting software and hypervisors
+static VIRTUAL_DRIVES: &[&str] = &[
+    "DTSOFT", "ELBY CLONEDRIVE", "ALCOHOL", "MAGICISO", "POWERISO", "WINCDEMU",
+    "MSFT VIRTUAL", "VIRTUAL CD", "VIRTUAL DVD", "VIRTUAL BD", "QEMU", "VBOX", "VMWARE",
+];
+// Fastest CAV drives top out around 52x on the outer edge
+static MAX_PHYSICAL_SPEED: f64 = 60.0;
+// Physical drives speed up towards the outer tracks, images read at a flat rate
+static UNIFORM_SPEED_SPREAD: f64 = 0.01;
+static UNIFORM_SPEED_MIN_TRACKS: usize = 3;
+
+lazy_static! {
+    static ref VIRTUAL_DRIVE_MATCHER: AhoCorasick = AhoCorasickBuilder::new()
+        .ascii_case_insensitive(true)
+        .build(VIRTUAL_DRIVES)
+        .unwrap();
+}
+
+#[derive(Serialize, Deserialize, TS, Debug, Clone, Copy, PartialEq, Eq)]
+#[ts(export)]
+pub enum AuthenticitySignalKind {
+    VirtualDrive,
+    ImpossibleReadSpeed,
+    UniformPerfection,
+}
+
+#[derive(Serialize, Deserialize, TS, Debug)]
+#[ts(export)]
+pub struct AuthenticitySignal {
+    pub kind: AuthenticitySignalKind,
+    pub detail: String,
+}
+
+// Separate from the evaluators, none of these signals affect the score
+#[derive(Serialize, Deserialize, TS, Debug, Default)]
+#[ts(export)]
+pub struct Authenticity {
+    pub likely_virtual: bool,
+    pub signals: Vec<AuthenticitySignal>,
+}
+
+impl Authenticity {
+    pub fn from_parsed(parsed: &ParsedLogCombined) -> Vec<Self> {
+        parsed.parsed_logs.iter().map(Self::from_log).collect()
+    }
+
+    pub fn from_log(log: &ParsedLog) -> Self {
+        let signals: Vec<AuthenticitySignal> = [
+            virtual_drive(log),
+            impossible_read_speed(&log.tracks),
+            uniform_perfection(&log.tracks),
+        ].into_iter().flatten().collect();
+
+        // A known drive string is conclusive on its own, the speed patterns only together
+        let likely_virtual = signals.len() > 1 || signals.iter().any(|s| s.kind == AuthenticitySignalKind::VirtualDrive);
+
+        Self { likely_virtual, signals }
+    }
+}
+
+fn virtual_drive(log: &ParsedLog) -> Option<AuthenticitySignal> {
+    let matched = VIRTUAL_DRIVE_MATCHER.find(log.drive.as_ref())?;
+    Some(AuthenticitySignal {
+        kind: AuthenticitySignalKind::VirtualDrive,
+        detail: format!("Drive {} matches virtual drive {}", log.drive, VIRTUAL_DRIVES[matched.pattern().as_usize()]),
+    })
+}
+
+fn impossible_read_speed(tracks: &[TrackEntry]) -> Option<AuthenticitySignal> {
+    let fastest = tracks.iter()
+        .filter_map(|track| track.extraction_speed)
+        .filter(|speed| *speed > MAX_PHYSICAL_SPEED)
+        .max_by(|a, b| a.total_cmp(b))?;
+    Some(AuthenticitySignal {
+        kind: AuthenticitySignalKind::ImpossibleReadSpeed,
+        detail: format!("Extraction speed of {:.1}x exceeds what physical drives reach", fastest),
+    })
+}
+
+fn uniform_perfection(tracks: &[TrackEntry]) -> Option<AuthenticitySignal> {
+    let tracks: Vec<&TrackEntry> = tracks.iter().filter(|track| !track.is_range).collect();
+    if tracks.len() < UNIFORM_SPEED_MIN_TRACKS || tracks.iter().any(|track| error_count(&track.errors) > 0) {
+        return None;
+    }
+
+    let speeds: Vec<f64> = tracks.iter().filter_map(|track| track.extraction_speed).collect();
+    if speeds.len() != tracks.len() {
+        return None;
+    }
+
+    let fastest = speeds.iter().cloned().fold(f64::MIN, f64::max);
+    let slowest = speeds.iter().cloned().fold(f64::MAX, f64::min);
+    if fastest <= 0.0 || (fastest - slowest) / fastest > UNIFORM_SPEED_SPREAD {
+        return None;
+    }
+
+    Some(AuthenticitySignal {
+        kind: AuthenticitySignalKind::UniformPerfection,
+        detail: format!("All {} tracks are error-free and read at a flat {:.1}x", tracks.len(), fastest),
+    })
+}
+
+fn error_count(errors: &TrackError) -> u32 {
+    [
+        &errors.read, &errors.skip, &errors.jitter_generic, &errors.jitter_edge, &errors.jitter_atom,
+        &errors.drift, &errors.dropped, &errors.duplicated, &errors.damaged_sectors,
+        &errors.inconsistent_err_sectors, &errors.missing_samples,
+    ].iter().map(|data| data.count).sum()
+}
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+
+use rayon::prelude::*;
+
+use crate::error::CambiaError;
+use crate::evaluate::EvaluatorType;
+use crate::handler::{parse_log_bytes_with_options, ParseOptions};
+use crate::intern::Interner;
+use crate::response::CambiaResponse;
+
+#[derive(Debug, Clone, Default)]
+pub struct BatchOptions {
+    // Inputs with identical contents are parsed once, later copies point at the first one
+    pub dedup: bool,
+    pub parse: ParseOptions,
+    pub cancel: CancellationToken,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+// Nearly every entry is a parsed response, boxing it would only add an allocation per log
+#[allow(clippy::large_enum_variant)]
+pub enum BatchEntry {
+    Parsed(CambiaResponse),
+    Failed(CambiaError),
+    Duplicate(usize),
+    // Inputs that weren't started before the token was cancelled, a log that is already being parsed runs to completion
+    Cancelled,
+}
+
+impl From<Result<CambiaResponse, CambiaError>> for BatchEntry {
+    fn from(result: Result<CambiaResponse, CambiaError>) -> Self {
+        match result {
+            Ok(parsed) => BatchEntry::Parsed(parsed),
+            Err(e) => BatchEntry::Failed(e),
+        }
+    }
+}
+
+pub struct DiscGroup {
+    pub best: usize,
+    // Lower scoring logs of the same disc, in input order
+    pub alternatives: Vec<usize>,
+}
+
+pub fn parse_many<T>(inputs: &[T], options: &BatchOptions) -> Vec<BatchEntry>
+where
+    T: AsRef<[u8]> + Sync,
+{
+    parse_many_with(inputs, options, |_, _, _| {})
+}
+
+// The callback runs on the worker threads as soon as an entry is ready, completion order is not input order
+pub fn parse_many_with<T, F>(inputs: &[T], options: &BatchOptions, on_entry: F) -> Vec<BatchEntry>
+where
+    T: AsRef<[u8]> + Sync,
+    F: Fn(usize, &BatchEntry, &[u8]) + Sync,
+{
+    let refs: Vec<&T> = inputs.iter().collect();
+    parse_many_from(&refs, |input| Ok(*input), options, on_entry)
+}
+
+// Sources are loaded on the worker threads and dropped once the callback returns, so only logs in flight are held in memory
+pub fn parse_many_from<S, T, L, F>(sources: &[S], load: L, options: &BatchOptions, on_entry: F) -> Vec<BatchEntry>
+where
+    S: Sync,
+    T: AsRef<[u8]>,
+    L: Fn(&S) -> Result<T, CambiaError> + Sync,
+    F: Fn(usize, &BatchEntry, &[u8]) + Sync,
+{
+    let duplicates = if options.dedup { find_duplicates(sources, &load, options) } else { Vec::new() };
+    let parse_options = ParseOptions { interner: Some(Interner::new()), ..options.parse.clone() };
+
+    sources.par_iter().enumerate().map(|(idx, source)| {
+        if options.cancel.is_cancelled() || matches!(duplicates.get(idx), Some(DedupCheck::Cancelled)) {
+            return BatchEntry::Cancelled;
+        }
+
+        let raw = match load(source) {
+            Ok(raw) => raw,
+            Err(e) => {
+                let entry = BatchEntry::Failed(e);
+                on_entry(idx, &entry, &[]);
+                return entry;
+            },
+        };
+
+        let entry = match duplicates.get(idx) {
+            Some(DedupCheck::Duplicate(first)) => BatchEntry::Duplicate(*first),
+            _ => BatchEntry::from(parse_log_bytes_with_options(Vec::new(), raw.as_ref(), &parse_options)),
+        };
+        on_entry(idx, &entry, raw.as_ref());
+        entry
+    }).collect()
+}
+
+// Logs of the same disc share a MusicBrainz disc ID, the highest OPS score wins and ties go to the earlier log.
+// Groups are in order of each disc's first log, logs without a TOC are never grouped.
+pub fn best_per_disc(entries: &[BatchEntry]) -> Vec<DiscGroup> {
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    let mut disc_groups: HashMap<&str, usize> = HashMap::new();
+
+    for (idx, entry) in entries.iter().enumerate() {
+        let BatchEntry::Parsed(response) = entry else {
+            continue;
+        };
+
+        match response.parsed.parsed_logs.first().map(|log| log.toc.mbz.hash.as_str()).filter(|hash| !hash.is_empty()) {
+            Some(disc_id) => match disc_groups.get(disc_id) {
+                Some(&group) => groups[group].push(idx),
+                None => {
+                    disc_groups.insert(disc_id, groups.len());
+                    groups.push(vec![idx]);
+                },
+            },
+            None => groups.push(vec![idx]),
+        }
+    }
+
+    groups.into_iter().map(|group| {
+        let score = |idx: usize| match &entries[idx] {
+            BatchEntry::Parsed(response) => response.score(EvaluatorType::OPS),
+            _ => None,
+        };
+        let best = group.iter().copied().fold(group[0], |best, idx| if score(idx) > score(best) { idx } else { best });
+        let alternatives = group.into_iter().filter(|&idx| idx != best).collect();
+        DiscGroup { best, alternatives }
+    }).collect()
+}
+
+enum DedupCheck {
+    Unique,
+    Duplicate(usize),
+    // Not hashed before the token was cancelled
+    Cancelled,
+}
+
+// Hashing is cheap compared to parsing, so duplicates are resolved upfront to keep the first occurrence deterministic
+fn find_duplicates<S, T, L>(sources: &[S], load: &L, options: &BatchOptions) -> Vec<DedupCheck>
+where
+    S: Sync,
+    T: AsRef<[u8]>,
+    L: Fn(&S) -> Result<T, CambiaError> + Sync,
+{
+    // Outer None for inputs skipped after cancellation, empty and unreadable inputs are rejected later anyway
+    let ids: Vec<Option<Option<Vec<u8>>>> = sources.par_iter().map(|source| {
+        if options.cancel.is_cancelled() {
+            return None;
+        }
+
+        Some(load(source).ok()
+            .filter(|raw| !raw.as_ref().is_empty())
+            .map(|raw| options.parse.id_hash.hash(raw.as_ref())))
+    }).collect();
+    let mut first_seen: HashMap<&[u8], usize> = HashMap::new();
+
+    ids.iter().enumerate().map(|(idx, id)| {
+        let Some(id) = id else {
+            return DedupCheck::Cancelled;
+        };
+        let Some(id) = id.as_deref() else {
+            return DedupCheck::Unique;
+        };
+        match first_seen.get(id) {
+            Some(&first) => DedupCheck::Duplicate(first),
+            None => {
+                first_seen.insert(id, idx);
+                DedupCheck::Unique
+            },
+        }
+    }).collect()
+}
@@ -0,0 +1,65 @@
+use serde::{Serialize, Deserialize};
+use ts_rs::TS;
+
+use crate::extract::Quartet;
+
+// Encoders that only produce lossy output
+static LOSSY_ENCODERS: &[&str] = &["lame", "oggenc", "oggenc2", "opusenc", "neroaacenc", "fdkaac", "faac", "mpcenc", "lossywav"];
+// qaac defaults to AAC, ALAC output has to be asked for
+static QAAC_LOSSLESS_OPTIONS: &[&str] = &["-A", "--alac"];
+static FLAC_VERIFY_OPTIONS: &[&str] = &["-V", "--verify"];
+static WAVPACK_VERIFY_OPTIONS: &[&str] = &["-v"];
+static TAG_STRIPPING_OPTIONS: &[&str] = &["--discard-comments", "--discard-pictures", "--remove-all-tags"];
+
+// External program EAC hands the extracted WAV to, as listed under the output options
+#[derive(Serialize, Deserialize, PartialEq, TS, Clone)]
+#[ts(export)]
+pub struct ExternalCompressor {
+    pub executable: String,
+    // Lowercased executable name without the path or extension, e.g. flac
+    pub encoder: String,
+    pub options: String,
+    pub lossy: bool,
+    // Unsupported for encoders that don't have a verify option
+    pub verify: Quartet,
+    pub strips_tags: bool,
+}
+
+impl ExternalCompressor {
+    pub fn new(executable: &str, options: &str) -> Self {
+        let executable = executable.trim();
+        let options = options.trim();
+        let encoder = Self::encoder_name(executable);
+        let args: Vec<&str> = options.split_whitespace().collect();
+        let has_any = |flags: &[&str]| args.iter().any(|arg| flags.contains(arg));
+        let quartet = |flags: &[&str]| if has_any(flags) { Quartet::True } else { Quartet::False };
+
+        let lossy = match encoder.as_str() {
+            "qaac" | "qaac64" => !has_any(QAAC_LOSSLESS_OPTIONS),
+            encoder => LOSSY_ENCODERS.contains(&encoder),
+        };
+        let verify = match encoder.as_str() {
+            "flac" => quartet(FLAC_VERIFY_OPTIONS),
+            "wavpack" => quartet(WAVPACK_VERIFY_OPTIONS),
+            _ => Quartet::Unsupported,
+        };
+
+        ExternalCompressor {
+            executable: executable.to_owned(),
+            encoder,
+            options: options.to_owned(),
+            lossy,
+            verify,
+            strips_tags: has_any(TAG_STRIPPING_OPTIONS),
+        }
+    }
+
+    // Logs carry Windows paths, which std::path doesn't split on other platforms
+    fn encoder_name(executable: &str) -> String {
+        let filename = executable.trim_matches('"').rsplit(['\\', '/']).next().unwrap_or_default().to_ascii_lowercase();
+        match filename.strip_suffix(".exe") {
+            Some(stem) => stem.to_owned(),
+            None => filename,
+        }
+    }
+}
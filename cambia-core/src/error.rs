@@ -3,20 +3,49 @@ use std::fmt;
 use serde::{Serialize, Deserialize};
 use ts_rs::TS;
 
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default, TS, strum_macros::Display)]
+#[ts(export)]
+pub enum CambiaErrorKind {
+    EmptyInput,
+    // No text encoding could be detected for the log
+    Decoding,
+    // Ripper is recognised, but there's no parser for it yet
+    UnsupportedRipper,
+    UnknownFormat,
+    // Nothing in the file resembles a rip log, e.g. player or burner logs sharing the extension
+    NotRipLog,
+    Io,
+    #[default]
+    Other,
+}
+
 #[derive(Serialize, Deserialize, Debug, TS)]
 #[ts(export)]
 pub struct CambiaError {
     pub id: Vec<u8>,
     pub message: String,
+    pub kind: CambiaErrorKind,
+    // Byte offset into the log that caused the failure, when it can be pinned down
+    pub offset: Option<usize>,
 }
 
 impl CambiaError {
     pub fn new(id: Vec<u8>, _message: &str) -> Self {
-        CambiaError { id, message: _message.to_string() }
+        CambiaError { id, message: _message.to_string(), kind: CambiaErrorKind::default(), offset: None }
     }
 
     pub fn new_anon(_message: &str) -> Self {
-        CambiaError { id: Vec::new(), message: _message.to_string() }
+        CambiaError { id: Vec::new(), message: _message.to_string(), kind: CambiaErrorKind::default(), offset: None }
+    }
+
+    pub fn with_kind(mut self, kind: CambiaErrorKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    pub fn with_offset(mut self, offset: Option<usize>) -> Self {
+        self.offset = offset;
+        self
     }
 }
 
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use serde::{Serialize, Deserialize};
 use ts_rs::TS;
 
@@ -7,7 +9,7 @@ pub mod gazelle_evaluate;
 // #[cfg(feature = "cambia_ev")]
 // pub mod cambia_evaluate;
 
-#[derive(Serialize, Deserialize, TS)]
+#[derive(Serialize, Deserialize, TS, Clone, Copy, PartialEq, Eq)]
 #[ts(export)]
 pub enum EvaluatorType {
     Cambia,
@@ -72,15 +74,20 @@ pub enum EvaluationUnitField {
     InconsistentErrorSectors,
     DamagedSector,
     Abort,
+    MissingTracks,
 }
 
 // This holds the reasoning for the smallest unit of evaluation
 #[derive(Serialize, Deserialize, TS, Hash, PartialEq, Eq, Clone)]
 #[ts(export)]
 pub struct EvaluationUnitData {
+    // Name of the deduction that produced this unit, stable across wording changes
+    #[ts(type = "string")]
+    pub code: Cow<'static, str>,
     pub scope: EvaluationUnitScope,
     pub field: EvaluationUnitField,
-    pub message: String,
+    #[ts(type = "string")]
+    pub message: Cow<'static, str>,
     pub class: EvaluationUnitClass,
 }
 
@@ -105,6 +112,8 @@ pub struct Evaluation {
 pub struct EvaluationUnit {
     pub unit_score: String,
     pub data: EvaluationUnitData,
+    // Whether the unit counts toward the combined score, combined logs drop duplicates and deductions overridden by other logs
+    pub applied: bool,
 }
 
 // All evaluators are required to implement this
@@ -114,18 +123,23 @@ pub trait Evaluator {
 }
 
 impl EvaluationUnitData {
-    pub fn new(scope: EvaluationUnitScope, field: EvaluationUnitField, message: &str, class: EvaluationUnitClass) -> Self {
-        EvaluationUnitData { scope, field, message: message.to_string(), class }
+    pub fn new(scope: EvaluationUnitScope, field: EvaluationUnitField, message: &'static str, class: EvaluationUnitClass) -> Self {
+        EvaluationUnitData { code: Cow::Borrowed(""), scope, field, message: Cow::Borrowed(message), class }
+    }
+
+    pub fn with_code(mut self, code: &'static str) -> Self {
+        self.code = Cow::Borrowed(code);
+        self
     }
 }
 
 impl EvaluationUnit {
     pub fn new(unit_score: String, data: EvaluationUnitData) -> Self {
-        EvaluationUnit { unit_score, data }
+        EvaluationUnit { unit_score, data, applied: true }
     }
 
     pub fn new_from_u32(unit_score: u32, data: EvaluationUnitData) -> Self {
-        EvaluationUnit { unit_score: unit_score.to_string(), data }
+        EvaluationUnit { unit_score: unit_score.to_string(), data, applied: true }
     }
 }
 
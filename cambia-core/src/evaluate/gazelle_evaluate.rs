@@ -1,5 +1,5 @@
 use serde::{Deserialize, Serialize};
-use strum_macros::EnumIter;
+use strum_macros::{EnumIter, IntoStaticStr};
 
 use crate::parser::ParsedLog;
 
@@ -23,7 +23,7 @@ pub trait GazelleDeduction {
     fn deduct(&self, parsed_log: &ParsedLog) -> EvaluationUnit;
 }
 
-#[derive(Serialize, Deserialize, EnumIter, Clone, Copy)]
+#[derive(Serialize, Deserialize, EnumIter, IntoStaticStr, Clone, Copy)]
 pub enum GazelleDeductionFail {
     UnknownEncoding,
     UnknownRipper,
@@ -31,7 +31,7 @@ pub enum GazelleDeductionFail {
     CouldNotParseWhipper,
 }
 
-#[derive(Serialize, Deserialize, EnumIter, Clone, Copy)]
+#[derive(Serialize, Deserialize, EnumIter, IntoStaticStr, Clone, Copy)]
 pub enum GazelleDeductionRelease {
     VirtualDrive,
     NullDrive,
@@ -58,6 +58,7 @@ pub enum GazelleDeductionRelease {
     CombinedOffsetUnverifiable,
     RippedWithCompressionOffset,
     RangeRip,
+    MissingTracks,
     TestAndCopyNotUsed,
     RipModeNotSecure,
     NotPressedCd,
@@ -70,11 +71,14 @@ pub enum GazelleDeductionRelease {
     NormalizationUsed,
     IncorrectGapHandling,
     Id3OnFlac,
+    LossyCompressor,
+    CompressorNoVerify,
+    CompressorStripsTags,
     NotSecureCrcMismatch,
     NotSecureNoTC,
 }
 
-#[derive(Serialize, Deserialize, EnumIter, Clone, Copy)]
+#[derive(Serialize, Deserialize, EnumIter, IntoStaticStr, Clone, Copy)]
 pub enum GazelleDeductionTrack {
     CouldNotVerifyFilenameTooLong,
     CouldNotVerifyFilenameOrExt,
@@ -284,6 +288,12 @@ impl GazelleDeductionData for GazelleDeductionRelease {
                 "Range rip detected",
                 EvaluationUnitClass::Bad
             ),
+            GazelleDeductionRelease::MissingTracks => EvaluationUnitData::new(
+                EvaluationUnitScope::Release,
+                EvaluationUnitField::MissingTracks,
+                "Not all tracks in the TOC were extracted",
+                EvaluationUnitClass::Bad
+            ),
             GazelleDeductionRelease::TestAndCopyNotUsed => EvaluationUnitData::new(
                 EvaluationUnitScope::Release,
                 EvaluationUnitField::TestAndCopy,
@@ -368,6 +378,24 @@ impl GazelleDeductionData for GazelleDeductionRelease {
                 "ID3 tags should not be added to FLAC files - they are mainly for MP3 files.",
                 EvaluationUnitClass::Neutral
             ),
+            GazelleDeductionRelease::LossyCompressor => EvaluationUnitData::new(
+                EvaluationUnitScope::Release,
+                EvaluationUnitField::Encoder,
+                "External compressor produces lossy output",
+                EvaluationUnitClass::Critical
+            ),
+            GazelleDeductionRelease::CompressorNoVerify => EvaluationUnitData::new(
+                EvaluationUnitScope::Release,
+                EvaluationUnitField::Encoder,
+                "External compressor does not verify the encoded files",
+                EvaluationUnitClass::Neutral
+            ),
+            GazelleDeductionRelease::CompressorStripsTags => EvaluationUnitData::new(
+                EvaluationUnitScope::Release,
+                EvaluationUnitField::Tag,
+                "External compressor options discard tags",
+                EvaluationUnitClass::Neutral
+            ),
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::{cmp::min, collections::{HashMap, HashSet}};
 
-use crate::{evaluate::{Evaluator, EvaluationCombined, EvaluationUnit, Evaluation, EvaluatorType, EvaluationUnitScope}, parser::{ParsedLogCombined, ParsedLog}, extract::{Ripper, Quartet, MediaType, ReadMode, Gap}, track::TrackEntry, integrity::Integrity, drive::{DriveUtils, DriveMatchQuality}};
+use crate::{evaluate::{Evaluator, EvaluationCombined, EvaluationUnit, Evaluation, EvaluatorType, EvaluationUnitScope, EvaluationUnitClass}, parser::{ParsedLogCombined, ParsedLog}, extract::{Ripper, Quartet, MediaType, ReadMode, Gap}, track::TrackEntry, integrity::Integrity, drive::{DriveUtils, DriveMatchQuality}};
 
 use super::{GazelleDeductionData, GazelleDeductionFail, GazelleDeductionRelease, GazelleDeductionTrack, GazelleDeduction};
 
@@ -24,12 +24,40 @@ static WHIPPER_VERSION_THRESH: Version = Version {
     build: BuildMetadata::EMPTY,
 };
 
+// Rule weights that differ between rulesets built on the OPS rules
+#[derive(Debug, Clone, Copy)]
+pub struct OpsProfile {
+    // Points deducted when null samples aren't used in CRC calculations, 0 keeps it as an informational note
+    pub null_samples_deduction: u32,
+}
+
+impl Default for OpsProfile {
+    fn default() -> Self {
+        Self { null_samples_deduction: 5 }
+    }
+}
+
 #[derive(Default)]
-pub struct OpsEvaluator;
+pub struct OpsEvaluator {
+    profile: OpsProfile,
+}
 
 impl OpsEvaluator {
     pub fn new() -> Self {
-        Self
+        Self::default()
+    }
+
+    pub fn with_profile(profile: OpsProfile) -> Self {
+        Self { profile }
+    }
+
+    fn apply_profile(&self, deduction: &mut EvaluationUnit, data: GazelleDeductionRelease) {
+        if let GazelleDeductionRelease::NullSamplesNotUsed = data {
+            deduction.unit_score = self.profile.null_samples_deduction.to_string();
+            if self.profile.null_samples_deduction == 0 {
+                deduction.data.class = EvaluationUnitClass::Neutral;
+            }
+        }
     }
 
     pub fn check_fail(parsed_log: &ParsedLog, data: GazelleDeductionFail) -> bool {
@@ -66,7 +94,7 @@ impl OpsEvaluator {
             GazelleDeductionRelease::VirtualDrive => parsed_log.drive.to_lowercase().contains("generic dvd-rom scsi cdrom device"),
             GazelleDeductionRelease::NullDrive => parsed_log.drive.to_lowercase().contains("(null) (null) (revision (null))"),
             GazelleDeductionRelease::IncorrectReadOffset => {
-                match DriveUtils::fuzzy_search_model(parsed_log.drive.clone()) {
+                match DriveUtils::fuzzy_search_model(parsed_log.drive.to_string()) {
                     DriveMatchQuality::STRONG(matched_offsets) => {
                         match parsed_log.read_offset {
                             Some(read_offset) => {
@@ -81,7 +109,7 @@ impl OpsEvaluator {
                 }
             },
             GazelleDeductionRelease::DriveNotFoundDb => {
-                match DriveUtils::fuzzy_search_model(parsed_log.drive.clone()) {
+                match DriveUtils::fuzzy_search_model(parsed_log.drive.to_string()) {
                     DriveMatchQuality::STRONG(matched_offsets) => {
                         matched_offsets.iter().all(|offset| offset.is_none())
                     }
@@ -94,7 +122,7 @@ impl OpsEvaluator {
             GazelleDeductionRelease::EacVersionOld => parsed_log.ripper == Ripper::EAC && (parsed_log.ripper_version == "Unknown" || parsed_log.ripper_version.cmp(&String::from("0.99")).is_lt()),
             GazelleDeductionRelease::XldNoChecksum => false,
             GazelleDeductionRelease::Mp3Log => !parsed_log.audio_encoder.is_empty() && parsed_log.audio_encoder.iter().all(|encoder| encoder.contains("mp3") || encoder.contains("lame")),
-            GazelleDeductionRelease::CouldNotVerifyDrive => parsed_log.drive == "Unknown Drive",
+            GazelleDeductionRelease::CouldNotVerifyDrive => &*parsed_log.drive == "Unknown Drive",
             GazelleDeductionRelease::CouldNotVerifyMedia => parsed_log.ripper == Ripper::XLD && parsed_log.ripper_version.cmp(&String::from("20130127")).is_ge() && parsed_log.media_type == MediaType::Unknown, 
             GazelleDeductionRelease::CouldNotVerifyReadMode => parsed_log.read_mode == ReadMode::Unknown,
             GazelleDeductionRelease::CouldNotVerifyMaxRetry => false, // TODO: XLD specific prop, does not affect scoring
@@ -122,6 +150,7 @@ impl OpsEvaluator {
                 }
                 false
             },
+            GazelleDeductionRelease::MissingTracks => !parsed_log.missing_tracks.is_empty(),
             GazelleDeductionRelease::TestAndCopyNotUsed => {
                 for track in &parsed_log.tracks {
                     if track.aborted {
@@ -145,6 +174,13 @@ impl OpsEvaluator {
                 let id3_valid_encoder = parsed_log.audio_encoder.iter().any(|encoder| encoder.contains("mp3") || encoder.contains("lame"));
                 parsed_log.id3_enabled == Quartet::True && !id3_valid_encoder
             },
+            // MP3 logs are already invalidated by Mp3Log
+            GazelleDeductionRelease::LossyCompressor => {
+                parsed_log.compressor.as_ref().is_some_and(|compressor| compressor.lossy)
+                    && !Self::check_release(parsed_log, GazelleDeductionRelease::Mp3Log)
+            },
+            GazelleDeductionRelease::CompressorNoVerify => parsed_log.compressor.as_ref().is_some_and(|compressor| compressor.verify == Quartet::False),
+            GazelleDeductionRelease::CompressorStripsTags => parsed_log.compressor.as_ref().is_some_and(|compressor| compressor.strips_tags),
             GazelleDeductionRelease::NotSecureCrcMismatch => {
                 if parsed_log.read_mode == ReadMode::Secure || (parsed_log.ripper == Ripper::XLD && parsed_log.read_mode == ReadMode::Paranoid) {
                     return false;
@@ -217,7 +253,7 @@ impl GazelleDeduction for GazelleDeductionFail {
             GazelleDeductionFail::WhipperVersionLowerLimit => 100,
             GazelleDeductionFail::CouldNotParseWhipper => 100,
         };
-        EvaluationUnit::new_from_u32(deduction_score, self.get_deduction_data())
+        EvaluationUnit::new_from_u32(deduction_score, self.get_deduction_data().with_code(self.into()))
     }
 }
 
@@ -249,6 +285,7 @@ impl GazelleDeduction for GazelleDeductionRelease {
             GazelleDeductionRelease::CouldNotVerifyAlbumGain => 0,
             GazelleDeductionRelease::RippedWithCompressionOffset => 0,
             GazelleDeductionRelease::RangeRip => 30,
+            GazelleDeductionRelease::MissingTracks => 20,
             GazelleDeductionRelease::TestAndCopyNotUsed => 10,
             GazelleDeductionRelease::RipModeNotSecure => 20,
             GazelleDeductionRelease::NotPressedCd => 0,
@@ -261,10 +298,13 @@ impl GazelleDeduction for GazelleDeductionRelease {
             GazelleDeductionRelease::NormalizationUsed => 100,
             GazelleDeductionRelease::IncorrectGapHandling => 10,
             GazelleDeductionRelease::Id3OnFlac => 1,
+            GazelleDeductionRelease::LossyCompressor => 100,
+            GazelleDeductionRelease::CompressorNoVerify => 0,
+            GazelleDeductionRelease::CompressorStripsTags => 0,
             GazelleDeductionRelease::NotSecureCrcMismatch => 20,
             GazelleDeductionRelease::NotSecureNoTC => 40,
         };
-        EvaluationUnit::new_from_u32(deduction_score, self.get_deduction_data())
+        EvaluationUnit::new_from_u32(deduction_score, self.get_deduction_data().with_code(self.into()))
     }
 }
 
@@ -294,19 +334,22 @@ impl GazelleDeduction for GazelleDeductionTrack {
             GazelleDeductionTrack::InconsistenciesInErrorSectors(inconsistency_count) => min(*inconsistency_count, 10),
             GazelleDeductionTrack::DamagedSectors(damaged_sector_count) => min(*damaged_sector_count, 10),
         };
-        EvaluationUnit::new_from_u32(deduction_score, self.get_deduction_data())
+        EvaluationUnit::new_from_u32(deduction_score, self.get_deduction_data().with_code(self.into()))
     }
 }
 
 impl Evaluator for OpsEvaluator {
+    #[cfg_attr(feature = "instrument", tracing::instrument(level = "debug", skip_all))]
     fn evaluate_combined(&mut self, plc: &ParsedLogCombined) -> EvaluationCombined {
         let mut evaluations: Vec<Evaluation> = Vec::new();
         let mut track_deduction_map: HashMap<usize, Vec<EvaluationUnit>> = HashMap::new();
         let mut release_deduction_set: HashSet<EvaluationUnit> = HashSet::new();
+        // Log each track's deductions are taken from
+        let mut track_origin: HashMap<usize, usize> = HashMap::new();
 
         // This is wrong on so many levels but it's how OPS implements it
         // TODO: This probably isn't efficient, should drop storing entire deductions in the map and only keep the scores
-        for log in plc.parsed_logs.iter() {
+        for (idx, log) in plc.parsed_logs.iter().enumerate() {
             let evaluation = self.evaluate(log);
             let mut log_track_deduction_map: HashMap<usize, Vec<EvaluationUnit>> = HashMap::new();
 
@@ -336,6 +379,7 @@ impl Evaluator for OpsEvaluator {
             for t in start_track..=total_tracks {
                 if tracks_ripped.contains(&t) {
                     track_deduction_map.insert(t, log_track_deduction_map.remove(&t).unwrap_or_default().to_owned());
+                    track_origin.insert(t, idx);
                 }
             }
 
@@ -348,6 +392,15 @@ impl Evaluator for OpsEvaluator {
             release_deduction_set.remove(&mp3);
         }
 
+        // Tracks missing from one log don't count against the release when another log in the file extracted them
+        if plc.parsed_logs.len() > 1 {
+            let ripped: HashSet<u8> = plc.parsed_logs.iter().flat_map(|log| log.tracks.iter().map(|t| t.num)).collect();
+            if plc.parsed_logs.iter().flat_map(|log| log.missing_tracks.iter()).all(|num| ripped.contains(num)) {
+                let missing = GazelleDeductionRelease::MissingTracks.deduct(plc.parsed_logs.first().unwrap());
+                release_deduction_set.remove(&missing);
+            }
+        }
+
         // OPS evaluator seems to have this unholy chimera of a deduction that's neither release-level nor track-level
         // Should not be used in scoring unless the last log has it
         let nscm = GazelleDeductionRelease::NotSecureCrcMismatch.deduct(plc.parsed_logs.last().unwrap());
@@ -355,6 +408,17 @@ impl Evaluator for OpsEvaluator {
             release_deduction_set.remove(&nscm);
         }
 
+        // Flag what ended up in the combined score, a release deduction shared by several logs is only applied once
+        let mut release_pending = release_deduction_set.clone();
+        for (idx, evaluation) in evaluations.iter_mut().enumerate() {
+            for unit in evaluation.evaluation_units.iter_mut() {
+                unit.applied = match unit.data.scope {
+                    EvaluationUnitScope::Release => release_pending.remove(&*unit),
+                    EvaluationUnitScope::Track(t) => t.is_some_and(|t| track_origin.get(&(t as usize)) == Some(&idx)),
+                };
+            }
+        }
+
         // Deduction aggregates
         let release_deduction_score: i32 = release_deduction_set
                                             .into_iter()
@@ -366,9 +430,11 @@ impl Evaluator for OpsEvaluator {
                                             .sum();
         
         let combined_score: i32 = 100 - release_deduction_score - track_deduction_score;
+        tracing::debug!("OPS combined score {} (release deductions: {}, track deductions: {})", combined_score, release_deduction_score, track_deduction_score);
         EvaluationCombined::new(EvaluatorType::OPS, combined_score.to_string(), evaluations)
     }
 
+    #[cfg_attr(feature = "instrument", tracing::instrument(level = "debug", skip_all))]
     fn evaluate(&mut self, parsed_log: &ParsedLog) -> Evaluation {
         let mut score: i32 = 100_i32;
         let mut deductions: Vec<EvaluationUnit> = Vec::new();
@@ -386,7 +452,8 @@ impl Evaluator for OpsEvaluator {
             .par_bridge()
             .filter_map(|gazelle_deduction_release| {
                 if OpsEvaluator::check_release(parsed_log, gazelle_deduction_release) {
-                    let deduction = gazelle_deduction_release.deduct(parsed_log);
+                    let mut deduction = gazelle_deduction_release.deduct(parsed_log);
+                    self.apply_profile(&mut deduction, gazelle_deduction_release);
                     Some(deduction)
                 } else {
                     None
@@ -433,6 +500,7 @@ impl Evaluator for OpsEvaluator {
 
         deductions.append(&mut deductions_track);
         score -=score_track;
+        tracing::debug!("OPS log score {} with {} deductions", score, deductions.len());
         
         Evaluation::new(score.to_string(), deductions)
     }
@@ -1,7 +1,7 @@
 use serde::{Serialize, Deserialize};
 use ts_rs::TS;
 
-use crate::{toc::Toc, track::{AccurateRipUnit, TestAndCopy, TrackEntry, TrackError}, util::Time};
+use crate::{compressor::ExternalCompressor, toc::Toc, track::{AccurateRipUnit, TestAndCopy, TrackEntry, TrackError}, util::Time};
 
 #[derive(Serialize, Deserialize, PartialEq, TS)]
 #[ts(export)]
@@ -185,6 +185,10 @@ pub trait Extractor {
     fn extract_audio_encoder(&self) -> Vec<String> {
         Vec::new()
     }
+
+    fn extract_compressor(&self) -> Option<ExternalCompressor> {
+        None
+    }
 }
 
 pub trait TrackExtractor {
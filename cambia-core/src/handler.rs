@@ -1,14 +1,46 @@
+use std::time::Instant;
+
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
 use simple_text_decode::DecodedText;
-use xxhash_rust::xxh3::xxh3_64;
 
-use crate::error::CambiaError;
+use crate::error::{CambiaError, CambiaErrorKind};
+use crate::intern::Interner;
 use crate::util::{first_line};
 use crate::evaluate::{EvaluationCombined, Evaluator};
+use crate::extract::Ripper;
 use crate::parser::{ParserCombined, ParsedLogCombined};
-use crate::response::CambiaResponse;
+use crate::progress::{NoopObserver, ParseEvent, ParseObserver};
+use crate::response::{CambiaResponse, Diagnostics, IdHashAlgorithm};
+
+// Phrases shared by the logs of most rippers, supported or not
+static RIP_LOG_MARKERS: &[&str] = &[
+    "used drive", "read offset", "accuraterip", "copy crc", "crc32 hash", "table of contents",
+    "toc of the extracted cd", "extraction speed", "gap handling", "test crc",
+];
+static RIP_LOG_MIN_MARKERS: usize = 2;
+
+lazy_static! {
+    static ref RIP_LOG_MARKER_MATCHER: AhoCorasick = AhoCorasickBuilder::new()
+        .ascii_case_insensitive(true)
+        .build(RIP_LOG_MARKERS)
+        .unwrap();
+}
 
+// Number of distinct rip log phrases in the text, used to tell malformed rip logs from unrelated files
+pub fn rip_log_confidence(text: &str) -> usize {
+    let mut found = [false; RIP_LOG_MARKERS.len()];
+    for m in RIP_LOG_MARKER_MATCHER.find_iter(text) {
+        found[m.pattern().as_usize()] = true;
+    }
+    found.iter().filter(|&&f| f).count()
+}
+
+#[cfg_attr(feature = "instrument", tracing::instrument(level = "debug", skip_all))]
 pub fn detect_ripper(encoded_log: DecodedText) -> Result<Box<dyn ParserCombined>, CambiaError> {
-    match first_line(&encoded_log.text) {
+    let first = first_line(&encoded_log.text);
+    tracing::debug!("Detecting ripper from first line: {}", first);
+
+    match first {
         #[cfg(feature = "eac")]
         eac if eac.contains("Exact Audio Copy") || eac.contains("EAC") => Ok(Box::new(crate::parser::eac_parser::EacParser::new(encoded_log))),
         #[cfg(feature = "xld")]
@@ -17,47 +49,119 @@ pub fn detect_ripper(encoded_log: DecodedText) -> Result<Box<dyn ParserCombined>
         whipper if whipper.contains("Log created by: whipper") => Ok(Box::new(crate::parser::whipper_parser::WhipperParser::new(encoded_log))),
         #[cfg(feature = "cueripper")]
         cueripper if cueripper.contains("CUERipper") => Ok(Box::new(crate::parser::cueripper_parser::CueRipperParser::new(encoded_log))),
-        cyanrip if cyanrip.contains("cyanrip") => Err(CambiaError::new_anon("cyanrip not supported at the moment.")),
-        dbpa if dbpa.contains("dBpoweramp Release") => Err(CambiaError::new_anon("dBpoweramp not supported at the moment.")),
-        morituri if morituri.contains("Logfile created by: morituri") => Err(CambiaError::new_anon("morituri not supported at the moment.")),
-        ezcd if ezcd.contains("EZ CD Audio Converter") => Err(CambiaError::new_anon("EZ CD Audio Converter not supported at the moment.")),
-        rip if rip.contains("Rip ") && rip.contains(" Audio Extraction Log") => Err(CambiaError::new_anon("Rip (OS X) not supported at the moment.")),
-        freac if freac.contains("Conversion #") => Err(CambiaError::new_anon("fre:ac not supported at the moment.")),
-        _ => Err(CambiaError::new_anon("Unsupported file."))
+        cyanrip if cyanrip.contains("cyanrip") => Err(CambiaError::new_anon("cyanrip not supported at the moment.").with_kind(CambiaErrorKind::UnsupportedRipper)),
+        dbpa if dbpa.contains("dBpoweramp Release") => Err(CambiaError::new_anon("dBpoweramp not supported at the moment.").with_kind(CambiaErrorKind::UnsupportedRipper)),
+        morituri if morituri.contains("Logfile created by: morituri") => Err(CambiaError::new_anon("morituri not supported at the moment.").with_kind(CambiaErrorKind::UnsupportedRipper)),
+        ezcd if ezcd.contains("EZ CD Audio Converter") => Err(CambiaError::new_anon("EZ CD Audio Converter not supported at the moment.").with_kind(CambiaErrorKind::UnsupportedRipper)),
+        rip if rip.contains("Rip ") && rip.contains(" Audio Extraction Log") => Err(CambiaError::new_anon("Rip (OS X) not supported at the moment.").with_kind(CambiaErrorKind::UnsupportedRipper)),
+        freac if freac.contains("Conversion #") => Err(CambiaError::new_anon("fre:ac not supported at the moment.").with_kind(CambiaErrorKind::UnsupportedRipper)),
+        _ if rip_log_confidence(&encoded_log.text) >= RIP_LOG_MIN_MARKERS => Err(CambiaError::new_anon("Unsupported file.").with_kind(CambiaErrorKind::UnknownFormat)),
+        _ => Err(CambiaError::new_anon("Not a rip log.").with_kind(CambiaErrorKind::NotRipLog)),
     }
 }
 
-pub fn parse_log_bytes(id: Vec<u8>, log_raw: &Vec<u8>) -> Result<CambiaResponse, CambiaError> {
+// Logs that fail detection are usually binary or mis-encoded, the first invalid UTF-8 byte is a good place to start looking
+// A BOM means the input wasn't read as UTF-8, so the offset would point at the wrong place
+fn decoding_error(id: Vec<u8>, log_raw: &[u8]) -> CambiaError {
+    let offset = match simple_text_decode::has_bom(log_raw) {
+        true => None,
+        false => std::str::from_utf8(log_raw).err().map(|e| e.valid_up_to()),
+    };
+
+    CambiaError::new(id, "Could not detect the log encoding.")
+        .with_kind(CambiaErrorKind::Decoding)
+        .with_offset(offset)
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ParseOptions {
+    // Only used when the caller doesn't supply an ID
+    pub id_hash: IdHashAlgorithm,
+    pub diagnostics: bool,
+    // Shares drive names between the logs of a batch, left unset for one-off parses
+    pub interner: Option<Interner>,
+    #[cfg(feature = "ops_ev")]
+    pub ops_profile: crate::evaluate::gazelle_evaluate::ops_evaluate::OpsProfile,
+}
+
+pub fn parse_log_bytes(id: Vec<u8>, log_raw: &[u8]) -> Result<CambiaResponse, CambiaError> {
+    parse_log_bytes_with_options(id, log_raw, &ParseOptions::default())
+}
+
+pub fn parse_log_bytes_with_options(id: Vec<u8>, log_raw: &[u8], options: &ParseOptions) -> Result<CambiaResponse, CambiaError> {
+    parse_log_bytes_observed(id, log_raw, options, &NoopObserver)
+}
+
+#[cfg_attr(feature = "instrument", tracing::instrument(level = "debug", skip_all, fields(size = log_raw.len())))]
+pub fn parse_log_bytes_observed(id: Vec<u8>, log_raw: &[u8], options: &ParseOptions, observer: &dyn ParseObserver) -> Result<CambiaResponse, CambiaError> {
+    let started = Instant::now();
+
     if log_raw.is_empty() {
-        return Err(CambiaError::new(id, "Empty request body"));
+        return Err(CambiaError::new(id, "Empty request body").with_kind(CambiaErrorKind::EmptyInput));
     }
 
-    let res_id = if id.is_empty() { xxh3_64(&log_raw).to_be_bytes().to_vec() } else { id };
-    let encoded_log = DecodedText::new(&log_raw).unwrap_or_default();
+    let (res_id, id_hash) = if id.is_empty() { (options.id_hash.hash(log_raw), Some(options.id_hash)) } else { (id, None) };
+    let encoded_log = match DecodedText::new(log_raw) {
+        Ok(encoded_log) => encoded_log,
+        Err(_) => return Err(decoding_error(res_id, log_raw)),
+    };
 
     tracing::debug!("Log {}: {} encoding detected ", hex::encode(&res_id), encoded_log.orig_encoding);
 
-    let parsed_logs: ParsedLogCombined = match detect_ripper(encoded_log) {
-        Ok(parser) => parser.parse_combined(),
+    let (mut parsed_logs, parser_ripper): (ParsedLogCombined, Ripper) = match detect_ripper(encoded_log) {
+        Ok(parser) => {
+            observer.on_event(ParseEvent::RipperDetected(parser.ripper()));
+            (parser.parse_combined_observed(observer), parser.ripper())
+        },
         Err(mut e) => {
             e.id = res_id;
             return Err(e)
         },
     };
 
+    if let Some(interner) = options.interner.as_ref() {
+        parsed_logs.parsed_logs.iter_mut().for_each(|log| log.drive = interner.intern(&log.drive));
+    }
+
     let evaluation_combined: Vec<EvaluationCombined> = vec![
 		#[cfg(feature = "ops_ev")]
-        crate::evaluate::gazelle_evaluate::ops_evaluate::OpsEvaluator::new().evaluate_combined(&parsed_logs),
+        crate::evaluate::gazelle_evaluate::ops_evaluate::OpsEvaluator::with_profile(options.ops_profile).evaluate_combined(&parsed_logs),
 		// #[cfg(feature = "cambia_ev")]
 		// crate::evaluate::cambia_evaluate::CambiaEvaluator::new().evaluate_combined(&parsed_logs),
     ];
+
+    for evaluation in evaluation_combined.iter() {
+        observer.on_event(ParseEvent::EvaluatorFinished(evaluation.evaluator));
+    }
     
-    Ok(CambiaResponse::new(res_id, parsed_logs, evaluation_combined))
+    let diagnostics = options.diagnostics.then(|| Diagnostics {
+        duration_ms: started.elapsed().as_secs_f64() * 1000.0,
+        encoding: parsed_logs.encoding.clone(),
+        log_size: log_raw.len(),
+        parser: parser_ripper,
+        evaluators: evaluation_combined.iter().map(|evaluation| evaluation.evaluator).collect(),
+    });
+
+    let mut response = CambiaResponse::new(res_id, id_hash, parsed_logs, evaluation_combined);
+    response.diagnostics = diagnostics;
+
+    Ok(response)
+}
+
+// Parsing is CPU-bound, so the async variants move the work onto Tokio's blocking pool
+#[cfg(feature = "async")]
+pub async fn parse_log_bytes_async<T>(id: Vec<u8>, log_raw: T, options: ParseOptions) -> Result<CambiaResponse, CambiaError>
+where
+    T: AsRef<[u8]> + Send + 'static,
+{
+    tokio::task::spawn_blocking(move || parse_log_bytes_with_options(id, log_raw.as_ref(), &options))
+        .await
+        .unwrap_or_else(|e| Err(CambiaError::new_anon(&e.to_string())))
 }
 
 pub fn translate_log_bytes(log_raw: Vec<u8>) -> Result<String, CambiaError> {
     if log_raw.is_empty() {
-        return Err(CambiaError::new_anon("Empty request body"));
+        return Err(CambiaError::new_anon("Empty request body").with_kind(CambiaErrorKind::EmptyInput));
     }
 
     let encoded_log = DecodedText::new(&log_raw).unwrap_or_default();
@@ -68,4 +172,9 @@ pub fn translate_log_bytes(log_raw: Vec<u8>) -> Result<String, CambiaError> {
     }
 }
 
-
+#[cfg(feature = "async")]
+pub async fn translate_log_bytes_async(log_raw: Vec<u8>) -> Result<String, CambiaError> {
+    tokio::task::spawn_blocking(move || translate_log_bytes(log_raw))
+        .await
+        .unwrap_or_else(|e| Err(CambiaError::new_anon(&e.to_string())))
+}
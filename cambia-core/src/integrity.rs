@@ -26,6 +26,7 @@ pub trait IntegrityChecker {
         String::new()
     }
 
+    #[cfg_attr(feature = "instrument", tracing::instrument(level = "debug", skip_all))]
     fn get_checksum(&self) -> Checksum {
         let old = self.extract_checksum();
         let new = self.calculate_checksum();
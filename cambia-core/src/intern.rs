@@ -0,0 +1,27 @@
+use std::{collections::HashSet, sync::{Arc, RwLock}};
+
+// Drive names repeat across a batch, logs parsed with the same interner share a single allocation for each.
+// Created per batch, so strings from untrusted input never outlive it.
+#[derive(Debug, Clone, Default)]
+pub struct Interner(Arc<RwLock<HashSet<Arc<str>>>>);
+
+impl Interner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn intern(&self, value: &str) -> Arc<str> {
+        if let Some(interned) = self.0.read().unwrap().get(value) {
+            return interned.clone();
+        }
+
+        let mut pool = self.0.write().unwrap();
+        if let Some(interned) = pool.get(value) {
+            return interned.clone();
+        }
+
+        let interned: Arc<str> = Arc::from(value);
+        pool.insert(interned.clone());
+        interned
+    }
+}
@@ -7,9 +7,17 @@ pub mod parser;
 pub mod translate;
 pub mod integrity;
 pub mod toc;
+pub mod compressor;
 pub mod track;
 pub mod util;
 pub mod error;
 pub mod evaluate;
 pub mod response;
 pub mod drive;
+pub mod progress;
+pub mod batch;
+pub mod intern;
+pub mod metadata;
+pub mod authenticity;
+pub mod accurip;
+pub mod render;
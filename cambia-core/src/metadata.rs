@@ -0,0 +1,123 @@
+use serde::{Serialize, Deserialize};
+use simple_text_decode::DecodedText;
+use ts_rs::TS;
+
+use crate::parser::ParsedLogCombined;
+
+#[derive(Serialize, Deserialize, TS, Default)]
+#[ts(export)]
+pub struct ReleaseMetadata {
+    pub performer: Option<String>,
+    pub title: Option<String>,
+    pub tracks: Vec<TrackMetadata>,
+}
+
+#[derive(Serialize, Deserialize, TS, Default)]
+#[ts(export)]
+pub struct TrackMetadata {
+    pub num: u8,
+    pub performer: Option<String>,
+    pub title: Option<String>,
+    pub isrc: Option<String>,
+}
+
+impl ReleaseMetadata {
+    // Logs only carry the release line and filenames, so track titles are a best guess until a cue sheet is merged in
+    pub fn from_parsed(parsed: &ParsedLogCombined) -> Self {
+        let Some(log) = parsed.parsed_logs.first() else {
+            return Self::default();
+        };
+
+        // Combined logs can spread the tracks over several sections, the first section to rip a track wins
+        let mut tracks: Vec<TrackMetadata> = Vec::new();
+        for track in parsed.parsed_logs.iter().flat_map(|log| log.tracks.iter()).filter(|track| !track.is_range) {
+            if tracks.iter().any(|existing| existing.num == track.num) {
+                continue;
+            }
+            tracks.push(TrackMetadata {
+                num: track.num,
+                title: track.filenames.first().and_then(|f| title_from_filename(f)),
+                ..Default::default()
+            });
+        }
+        tracks.sort_by_key(|track| track.num);
+
+        Self {
+            performer: non_empty(&log.release_info.artist),
+            title: non_empty(&log.release_info.title),
+            tracks,
+        }
+    }
+
+    pub fn from_cue(cue: &str) -> Self {
+        let mut metadata = Self::default();
+
+        for line in cue.lines() {
+            let Some((command, value)) = line.trim().split_once(' ') else {
+                continue;
+            };
+            let value = value.trim().trim_matches('"');
+
+            match command.to_ascii_uppercase().as_str() {
+                "TRACK" => {
+                    let num = value.split_whitespace().next().and_then(|n| n.parse::<u8>().ok()).unwrap_or_default();
+                    metadata.tracks.push(TrackMetadata { num, ..Default::default() });
+                },
+                "PERFORMER" => match metadata.tracks.last_mut() {
+                    Some(track) => track.performer = non_empty(value),
+                    None => metadata.performer = non_empty(value),
+                },
+                "TITLE" => match metadata.tracks.last_mut() {
+                    Some(track) => track.title = non_empty(value),
+                    None => metadata.title = non_empty(value),
+                },
+                "ISRC" => if let Some(track) = metadata.tracks.last_mut() {
+                    track.isrc = non_empty(value);
+                },
+                _ => (),
+            }
+        }
+
+        metadata
+    }
+
+    pub fn from_cue_bytes(cue_raw: &[u8]) -> Self {
+        Self::from_cue(&DecodedText::new(cue_raw).unwrap_or_default().text)
+    }
+
+    // Values from the cue sheet take precedence over what was guessed from the log
+    pub fn merge(&mut self, other: ReleaseMetadata) {
+        if other.performer.is_some() {
+            self.performer = other.performer;
+        }
+        if other.title.is_some() {
+            self.title = other.title;
+        }
+
+        for other_track in other.tracks {
+            match self.tracks.iter_mut().find(|track| track.num == other_track.num) {
+                Some(track) => {
+                    if other_track.performer.is_some() { track.performer = other_track.performer; }
+                    if other_track.title.is_some() { track.title = other_track.title; }
+                    if other_track.isrc.is_some() { track.isrc = other_track.isrc; }
+                },
+                None => self.tracks.push(other_track),
+            }
+        }
+    }
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() { None } else { Some(value.to_owned()) }
+}
+
+// "C:\Rips\01 - Title.flac" -> "Title"
+fn title_from_filename(filename: &str) -> Option<String> {
+    let name = filename.rsplit(['\\', '/']).next().unwrap_or(filename);
+    let stem = name.rsplit_once('.').map_or(name, |(stem, _)| stem);
+    let title = stem
+        .trim_start_matches(|c: char| c.is_ascii_digit())
+        .trim_start_matches([' ', '-', '.', '_']);
+    non_empty(title)
+}
@@ -1,9 +1,13 @@
+use std::sync::Arc;
+
 pub use crate::extract::{self, Quartet, Ripper, ReadMode, Gap, Extractor, TrackExtractor};
+use crate::compressor::ExternalCompressor;
 use crate::toc::Toc;
 use crate::track::TrackEntry;
 pub use crate::translate::Translator;
 pub use crate::integrity::{Checksum, IntegrityChecker};
 use crate::translate::TranslatorCombined;
+use crate::progress::{ParseEvent, ParseObserver};
 
 #[cfg(feature = "eac")]
 pub mod eac_parser;
@@ -30,7 +34,8 @@ pub struct ParsedLog {
     pub language: String,
     pub read_offset: Option<i16>,
     pub combined_rw_offset: Option<i32>,
-    pub drive: String,
+    #[ts(type = "string")]
+    pub drive: Arc<str>,
     pub media_type: MediaType,
     pub accurate_stream: Quartet,
     pub defeat_audio_cache: Quartet,
@@ -46,8 +51,11 @@ pub struct ParsedLog {
     pub checksum: Checksum,
     pub toc: Toc,
     pub tracks: Vec<TrackEntry>,
+    // Audio tracks listed in the TOC without an entry in the log
+    pub missing_tracks: Vec<u8>,
     pub id3_enabled: Quartet,
     pub audio_encoder: Vec<String>,
+    pub compressor: Option<ExternalCompressor>,
 }
 
 #[derive(Serialize, Deserialize, TS)]
@@ -57,8 +65,30 @@ pub struct ParsedLogCombined {
     pub encoding: String,
 }
 
+impl ParsedLog {
+    // Range rips have no per-track entries and an unknown TOC gives nothing to compare against, so neither reports missing tracks
+    pub fn find_missing_tracks(toc: &Toc, tracks: &[TrackEntry]) -> Vec<u8> {
+        if tracks.is_empty() || tracks.iter().any(|track| track.is_range) {
+            return Vec::new();
+        }
+
+        // Data tracks are never extracted. Those of enhanced CDs come last in the TOC and are detected from the session gap,
+        // mixed-mode CDs carry theirs as track 1 which the TOC doesn't tell apart from audio, so a missing track 1 isn't reported.
+        let audio_entries = toc.raw.entries.len().saturating_sub(toc.raw.data_tracks as usize);
+        let maybe_data_track = |num: u8| num == 1 && toc.raw.data_tracks == 0;
+        toc.raw.entries[..audio_entries].iter()
+            .filter_map(|entry| u8::try_from(entry.track).ok())
+            .filter(|num| !maybe_data_track(*num) && !tracks.iter().any(|track| track.num == *num))
+            .collect()
+    }
+}
+
 pub trait Parser: Extractor + IntegrityChecker {
+    #[cfg_attr(feature = "instrument", tracing::instrument(level = "debug", skip_all))]
     fn parse(&mut self) -> ParsedLog {
+        let toc = self.extract_toc();
+        let tracks = self.extract_tracks();
+
         ParsedLog {
             ripper: self.extract_ripper(),
             ripper_version: self.extract_ripper_version(),
@@ -66,7 +96,7 @@ pub trait Parser: Extractor + IntegrityChecker {
             language: self.extract_language(),
             read_offset: self.extract_read_offset(),
             combined_rw_offset: self.extract_combined_rw_offset(),
-            drive: self.extract_drive(),
+            drive: Arc::from(self.extract_drive()),
             media_type: self.extract_media_type(),
             accurate_stream: self.extract_accurate_stream(),
             defeat_audio_cache: self.extract_defeat_audio_cache(),
@@ -80,10 +110,12 @@ pub trait Parser: Extractor + IntegrityChecker {
             read_mode: self.extract_read_mode(),
             gap_handling: self.extract_gap_handling(),
             checksum: self.get_checksum(),
-            toc: self.extract_toc(),
-            tracks: self.extract_tracks(),
+            missing_tracks: ParsedLog::find_missing_tracks(&toc, &tracks),
+            toc,
+            tracks,
             id3_enabled: self.extract_id3_enabled(),
             audio_encoder: self.extract_audio_encoder(),
+            compressor: self.extract_compressor(),
         }
     }
 }
@@ -91,10 +123,20 @@ pub trait Parser: Extractor + IntegrityChecker {
 pub trait ParserSingle: Translator {}
 
 pub trait ParserCombined: TranslatorCombined {
+    fn ripper(&self) -> Ripper;
+
     fn parse_combined(&self) -> ParsedLogCombined;
+
+    fn parse_combined_observed(&self, observer: &dyn ParseObserver) -> ParsedLogCombined {
+        let parsed = self.parse_combined();
+        let total = parsed.parsed_logs.len();
+        (0..total).for_each(|index| observer.on_event(ParseEvent::LogParsed { index, total }));
+        parsed
+    }
 }
 
 pub trait ParserTrack: TrackExtractor {
+    #[cfg_attr(feature = "instrument", tracing::instrument(level = "trace", skip_all))]
     fn parse_track(&self) -> TrackEntry {
         TrackEntry {
             num: self.extract_num(),
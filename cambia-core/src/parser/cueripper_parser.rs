@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use simple_text_decode::DecodedText;
 
 use crate::{translate::TranslatorCombined, extract::{Ripper, Extractor, Quartet, ReadMode, Gap, TrackExtractor}, track::{TrackEntry, TestAndCopy}, toc::{TocEntry, Toc, TocRaw}, util::Time};
@@ -26,8 +28,8 @@ pub struct CueRipperParser {
     encoded_log: DecodedText,
 }
 
-pub struct CueRipperParserSingle {
-    log: String,
+pub struct CueRipperParserSingle<'a> {
+    log: &'a str,
     language: String,
 }
 
@@ -46,8 +48,12 @@ impl CueRipperParser {
 }
 
 impl ParserCombined for CueRipperParser {
+    fn ripper(&self) -> Ripper {
+        Ripper::CueRipper
+    }
+
     fn parse_combined(&self) -> ParsedLogCombined {        
-        let parsed_logs: Vec<ParsedLog> = vec![CueRipperParserSingle::new(self.encoded_log.text.trim().to_owned()).parse()];
+        let parsed_logs: Vec<ParsedLog> = vec![CueRipperParserSingle::new(self.encoded_log.text.trim()).parse()];
 
         ParsedLogCombined {
             parsed_logs,
@@ -68,8 +74,8 @@ impl<'a> CueRipperParserTrack<'a> {
     }
 }
 
-impl CueRipperParserSingle {
-    pub fn new(log: String) -> Self {
+impl<'a> CueRipperParserSingle<'a> {
+    pub fn new(log: &'a str) -> Self {
         Self {
             log,
             language: String::from("English"),
@@ -77,56 +83,63 @@ impl CueRipperParserSingle {
     }
 }
 
-impl Parser for CueRipperParserSingle {
+impl<'a> Parser for CueRipperParserSingle<'a> {
     fn parse(&mut self) -> ParsedLog {
-        let captures = EAC_VARIANT.captures(&self.log);
+        let captures = EAC_VARIANT.captures(self.log);
         let parsed_log: ParsedLog = match captures {
             Some(_) => {
-                let mut eac_variant = EacParserSingle::new(self.log.trim().to_string()).parse();
+                let mut eac_variant = EacParserSingle::new(self.log.trim()).parse();
                 eac_variant.ripper = self.extract_ripper();
                 eac_variant.ripper_version = self.extract_ripper_version();
                 eac_variant.checksum = self.get_checksum();
                 eac_variant.id3_enabled = self.extract_id3_enabled();
                 eac_variant
             },
-            None => ParsedLog {
-                ripper: self.extract_ripper(),
-                ripper_version: self.extract_ripper_version(),
-                release_info: self.extract_release_info(),
-                language: self.extract_language(),
-                read_offset: self.extract_read_offset(),
-                combined_rw_offset: self.extract_combined_rw_offset(),
-                drive: self.extract_drive(),
-                media_type: self.extract_media_type(),
-                accurate_stream: self.extract_accurate_stream(),
-                defeat_audio_cache: self.extract_defeat_audio_cache(),
-                use_c2: self.extract_use_c2(),
-                overread: self.extract_overread(),
-                fill_silence: self.extract_fill_silence(),
-                delete_silence: self.extract_delete_silence(),
-                use_null_samples: self.extract_use_null_samples(),
-                test_and_copy: self.extract_test_and_copy(),
-                normalize: self.extract_normalize(),
-                read_mode: self.extract_read_mode(),
-                gap_handling: self.extract_gap_handling(),
-                checksum: self.get_checksum(),
-                toc: self.extract_toc(),
-                tracks: self.extract_tracks(),
-                id3_enabled: self.extract_id3_enabled(),
-                audio_encoder: self.extract_audio_encoder(),
+            None => {
+                let toc = self.extract_toc();
+                let tracks = self.extract_tracks();
+
+                ParsedLog {
+                    ripper: self.extract_ripper(),
+                    ripper_version: self.extract_ripper_version(),
+                    release_info: self.extract_release_info(),
+                    language: self.extract_language(),
+                    read_offset: self.extract_read_offset(),
+                    combined_rw_offset: self.extract_combined_rw_offset(),
+                    drive: Arc::from(self.extract_drive()),
+                    media_type: self.extract_media_type(),
+                    accurate_stream: self.extract_accurate_stream(),
+                    defeat_audio_cache: self.extract_defeat_audio_cache(),
+                    use_c2: self.extract_use_c2(),
+                    overread: self.extract_overread(),
+                    fill_silence: self.extract_fill_silence(),
+                    delete_silence: self.extract_delete_silence(),
+                    use_null_samples: self.extract_use_null_samples(),
+                    test_and_copy: self.extract_test_and_copy(),
+                    normalize: self.extract_normalize(),
+                    read_mode: self.extract_read_mode(),
+                    gap_handling: self.extract_gap_handling(),
+                    checksum: self.get_checksum(),
+                    missing_tracks: ParsedLog::find_missing_tracks(&toc, &tracks),
+                    toc,
+                    tracks,
+                    id3_enabled: self.extract_id3_enabled(),
+                    audio_encoder: self.extract_audio_encoder(),
+                    compressor: self.extract_compressor(),
+                }
             },
         };
         parsed_log
     }
 }
 
-impl Extractor for CueRipperParserSingle {
+impl<'a> Extractor for CueRipperParserSingle<'a> {
     fn extract_ripper(&self) -> Ripper {
         Ripper::CueRipper
     }
 
     fn extract_ripper_version(&self) -> String {
-        let captures = RIPPER_VERSION.captures(&self.log);
+        let captures = RIPPER_VERSION.captures(self.log);
         match captures {
             Some(captures) => captures.get(1).unwrap().as_str().trim_start_matches('V').to_string(),
             None => String::from("Unknown"),
@@ -138,7 +151,7 @@ impl Extractor for CueRipperParserSingle {
     }
 
     fn extract_drive(&self) -> String {
-        let captures = USED_DRIVE.captures(&self.log);
+        let captures = USED_DRIVE.captures(self.log);
         match captures {
             Some(captures) => captures.get(2).unwrap().as_str().trim().to_string(),
             None => String::default(),
@@ -146,12 +159,12 @@ impl Extractor for CueRipperParserSingle {
     }
 
     fn extract_read_offset(&self) -> Option<i16> {
-        let captures = READ_OFFSET_CORRECTION.captures(&self.log);
+        let captures = READ_OFFSET_CORRECTION.captures(self.log);
         captures.map(|captures| captures.get(2).unwrap().as_str().parse::<i16>().unwrap())
     }
 
     fn extract_read_mode(&self) -> ReadMode {
-        let captures = READ_MODE.captures(&self.log);
+        let captures = READ_MODE.captures(self.log);
         match captures {
             Some(captures) => {
                 let value = captures.get(2).unwrap().as_str().parse::<i8>().unwrap_or(-1);
@@ -177,7 +190,7 @@ impl Extractor for CueRipperParserSingle {
 
     fn extract_toc(&self) -> Toc {
         let mut entries: Vec<TocEntry> = Vec::new();
-        let captures_all = TOC.captures_iter(&self.log);
+        let captures_all = TOC.captures_iter(self.log);
 
         for captures in captures_all  {
             entries.push(TocEntry::new(
@@ -195,9 +208,9 @@ impl Extractor for CueRipperParserSingle {
     fn extract_tracks(&self) -> Vec<TrackEntry> {
         let mut tracks: Vec<TrackEntry> = Vec::new();
 
-        let mut filename_all = FILENAME.captures_iter(&self.log);
-        let mut peak_crc_all = PEAK_CRC.captures_iter(&self.log);
-        let pregap_all = PREGAP.captures_iter(&self.log);
+        let mut filename_all = FILENAME.captures_iter(self.log);
+        let mut peak_crc_all = PEAK_CRC.captures_iter(self.log);
+        let pregap_all = PREGAP.captures_iter(self.log);
 
         for pregap in pregap_all {
             let track_parser = CueRipperParserTrack::new(
@@ -215,7 +228,7 @@ impl Extractor for CueRipperParserSingle {
     }
 }
 
-impl IntegrityChecker for CueRipperParserSingle {
+impl<'a> IntegrityChecker for CueRipperParserSingle<'a> {
     fn extract_checksum(&self) -> String {
         String::new()
     }
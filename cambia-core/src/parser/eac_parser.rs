@@ -1,14 +1,14 @@
 mod translation_table;
 mod rijndael;
 
-use std::str::FromStr;
+use std::{borrow::Cow, str::FromStr, sync::OnceLock};
 
-use aho_corasick::AhoCorasickBuilder;
+use aho_corasick::{AhoCorasick, AhoCorasickBuilder};
 use phf::OrderedMap;
 use regex::{Regex, RegexBuilder};
 use rayon::prelude::*;
 
-use crate::{extract::{Extractor, Gap, Quartet, ReadMode, ReleaseInfo, Ripper, TrackExtractor}, integrity::IntegrityChecker, toc::{Toc, TocEntry, TocRaw}, track::{TestAndCopy, TrackEntry, TrackError, TrackErrorData, TrackErrorRange, AccurateRipUnit}, translate::{Translator, TranslatorCombined}, util::Time};
+use crate::{compressor::ExternalCompressor, extract::{Extractor, Gap, Quartet, ReadMode, ReleaseInfo, Ripper, TrackExtractor}, integrity::IntegrityChecker, progress::{NoopObserver, ParseEvent, ParseObserver}, toc::{Toc, TocEntry, TocRaw}, track::{TestAndCopy, TrackEntry, TrackError, TrackErrorData, TrackErrorRange, AccurateRipUnit}, translate::{Translator, TranslatorCombined}, util::Time};
 use simple_text_decode::DecodedText;
 
 use self::{translation_table::{LANGS, L_DUMMY_MAP, L_47AB3DF2_MAP}, rijndael::Rijndael};
@@ -42,6 +42,7 @@ lazy_static! {
     static ref GAP_HANDLING: Regex = Regex::new(r"Gap handling( *): (.+)").unwrap();
     static ref USED_OUTPUT_FMT: Regex = RegexBuilder::new(r"Used output format( *): (.*)(?P<fmt>flac|wav|mp3|m4a|ape|tta|ogg)").case_insensitive(true).build().unwrap();
     static ref CLI_ENCODER: Regex = Regex::new(r"Command line compressor( *): (.+)").unwrap();
+    static ref CLI_ENCODER_OPTIONS: Regex = Regex::new(r"Additional command line options( *): (.+)").unwrap();
 
     static ref TEST_AND_COPY: Regex = Regex::new(r"Test CRC ([0-9A-F]{8})").unwrap();
     static ref NORMALIZE: Regex = Regex::new(r"Normalize to( +): ([0-9% ]+)").unwrap();
@@ -67,15 +68,19 @@ lazy_static! {
     static ref AR_FOUND: Regex = Regex::new(r"Accurately ripped \(confidence (?P<cm>\d+)\)  \[(?P<sign>[A-F0-9]{8})\]  \(AR v(?P<version>\d+)\)").unwrap();
     static ref AR_MISMATCH: Regex = Regex::new(r"Cannot be verified as accurate \(confidence (?P<cm>\d+)\)  \[(?P<sign>[A-F0-9]{8})\], AccurateRip returned \[(?P<off_sign>[A-F0-9]{8})\]  \(AR v(?P<version>\d+)\)").unwrap();
     static ref AR_NO_DB: Regex = Regex::new(r"Track not present in AccurateRip database").unwrap();
+
+    // Automatons are built once per process instead of per log, table automatons only for the languages encountered
+    static ref LANG_KEYS: AhoCorasick = AhoCorasick::new(LANGS.iter().map(|lang| lang.localised_key.trim())).unwrap();
+    static ref LANG_TABLES: Vec<OnceLock<AhoCorasick>> = LANGS.iter().map(|_| OnceLock::new()).collect();
 }
 
 pub struct EacParser {
     encoded_log: DecodedText,
 }
 
-pub struct EacParserSingle {
-    log: String,
-    translated_log: String,
+pub struct EacParserSingle<'a> {
+    log: &'a str,
+    translated_log: Cow<'a, str>,
     language: String,
 }
 
@@ -94,15 +99,15 @@ impl EacParser {
     }
 }
 
-pub struct EacParserTrack {
+pub struct EacParserTrack<'a> {
     is_range: bool,
     use_null_samples: Quartet,
-    raw: String,
+    raw: &'a str,
 }
 
-impl EacParserSingle {
-    pub fn new(log: String) -> EacParserSingle {
-        let (language, translated_log) = EacParserSingle::translate(log.clone());
+impl<'a> EacParserSingle<'a> {
+    pub fn new(log: &'a str) -> EacParserSingle<'a> {
+        let (language, translated_log) = EacParserSingle::translate(log);
         EacParserSingle {
             log,
             translated_log,
@@ -141,12 +146,23 @@ impl EacParserSingle {
 }
 
 impl ParserCombined for EacParser {
+    fn ripper(&self) -> Ripper {
+        Ripper::EAC
+    }
+
     fn parse_combined(&self) -> ParsedLogCombined {
+        self.parse_combined_observed(&NoopObserver)
+    }
+
+    fn parse_combined_observed(&self, observer: &dyn ParseObserver) -> ParsedLogCombined {
         let split_logs = self.split_combined();
+        let total = split_logs.len();
 
-        let parsed_logs: Vec<ParsedLog> = split_logs.par_iter().map(
-            |split_log| EacParserSingle::new(split_log.trim().to_string()).parse()
-        ).collect();
+        let parsed_logs: Vec<ParsedLog> = split_logs.par_iter().enumerate().map(|(index, split_log)| {
+            let parsed_log = EacParserSingle::new(split_log.trim()).parse();
+            observer.on_event(ParseEvent::LogParsed { index, total });
+            parsed_log
+        }).collect();
         
         ParsedLogCombined {
             parsed_logs,
@@ -159,17 +175,17 @@ impl TranslatorCombined for EacParser {
     fn translate_combined(&self) -> String {
         let split_logs = self.split_combined();
 
-        let translated_logs: Vec<String> = split_logs.par_iter().map(
-            |split_log| EacParserSingle::translate(split_log.trim().to_owned()).1
+        let translated_logs: Vec<Cow<str>> = split_logs.par_iter().map(
+            |split_log| EacParserSingle::translate(split_log.trim()).1
         ).collect();
         
         translated_logs.join(SPLIT_SEP)
     }
 }
 
-impl Parser for EacParserSingle {}
+impl<'a> Parser for EacParserSingle<'a> {}
 
-impl Extractor for EacParserSingle {
+impl<'a> Extractor for EacParserSingle<'a> {
     fn extract_ripper(&self) -> Ripper {
         Ripper::EAC
     }
@@ -337,6 +353,17 @@ impl Extractor for EacParserSingle {
         }
     }
 
+    // Only applies to user defined encoders, built-in formats leave a stale command line in the log
+    fn extract_compressor(&self) -> Option<ExternalCompressor> {
+        if USED_OUTPUT_FMT.is_match(&self.translated_log) {
+            return None;
+        }
+
+        let executable = CLI_ENCODER.captures(&self.translated_log)?;
+        let options = CLI_ENCODER_OPTIONS.captures(&self.translated_log);
+        Some(ExternalCompressor::new(&executable[2], options.as_ref().map_or("", |c| &c[2])))
+    }
+
     fn extract_toc(&self) -> Toc {
         let mut entries: Vec<TocEntry> = Vec::new();
         let captures_all = TOC.captures_iter(&self.translated_log);
@@ -363,12 +390,12 @@ impl Extractor for EacParserSingle {
         let captures_all_range = RANGE_TRACKS.captures_iter(&self.translated_log);
 
         for captures in captures_all_split {
-            let track_parser = EacParserTrack::new(false, null_flag, captures.get(0).unwrap().as_str().to_string());
+            let track_parser = EacParserTrack::new(false, null_flag, captures.get(0).unwrap().as_str());
             tracks.push(track_parser.parse_track())
         }
 
         for captures in captures_all_range {
-            let track_parser = EacParserTrack::new(true, null_flag, captures.get(0).unwrap().as_str().to_string());
+            let track_parser = EacParserTrack::new(true, null_flag, captures.get(0).unwrap().as_str());
             tracks.push(track_parser.parse_track())
         }
 
@@ -376,35 +403,41 @@ impl Extractor for EacParserSingle {
     }
 }
 
-impl ParserTrack for EacParserTrack {}
+impl<'a> ParserTrack for EacParserTrack<'a> {}
 
-impl Translator for EacParserSingle {
-    fn translate(log: String) -> (String, String) {
+impl<'a> Translator for EacParserSingle<'a> {
+    #[cfg_attr(feature = "instrument", tracing::instrument(level = "debug", skip_all))]
+    fn translate(log: &str) -> (String, Cow<'_, str>) {
         let mut best_lang = &EacLanguage::default();
         let mut best_translated_log = String::new();
         let mut max_replacements = 0;
         
-        let matching_langs: Vec<&&EacLanguage> = LANGS.iter()
-            .filter(|cur_lang| log.contains(cur_lang.localised_key.trim()))
+        let mut matching_langs: Vec<usize> = LANG_KEYS
+            .find_overlapping_iter(log)
+            .map(|m| m.pattern().as_usize())
             .collect();
+        matching_langs.sort_unstable();
+        matching_langs.dedup();
         
-        for &log_lang in matching_langs.iter() {
+        for &lang_idx in matching_langs.iter() {
+            let log_lang = LANGS[lang_idx];
             if log_lang.lang_id == "47AB3DF2" {
                 // English doesn't need translation
-                return (log_lang.lang_native.to_owned(), log);
+                return (log_lang.lang_native.to_owned(), Cow::Borrowed(log));
             }
             
             tracing::debug!("Translating EAC log from {}", log_lang.lang_id);
-            let patterns = log_lang.table.keys();
-            let ac = AhoCorasickBuilder::new()
-                .match_kind(aho_corasick::MatchKind::LeftmostLongest)
-                .build(patterns)
-                .unwrap();
+            let ac = LANG_TABLES[lang_idx].get_or_init(|| {
+                AhoCorasickBuilder::new()
+                    .match_kind(aho_corasick::MatchKind::LeftmostLongest)
+                    .build(log_lang.table.keys())
+                    .unwrap()
+            });
             
             let mut translated_log = String::new();
             let mut replacements = 0;
             
-            ac.replace_all_with(&log, &mut translated_log, |_, k, v| {
+            ac.replace_all_with(log, &mut translated_log, |_, k, v| {
                 // Case-insensitive on k > 16 but not sure if it's really needed
                 let string_id = log_lang.table.get(k).unwrap();
                 if let Some(en_val) = &L_47AB3DF2_MAP.get(string_id) {
@@ -422,14 +455,15 @@ impl Translator for EacParserSingle {
         }
         
         if matching_langs.is_empty() || max_replacements == 0 {
-            return (EacLanguage::default().lang_native.to_owned(), log);
+            return (EacLanguage::default().lang_native.to_owned(), Cow::Borrowed(log));
         }
         
-        (best_lang.lang_native.to_owned(), best_translated_log)
+        tracing::debug!("Selected EAC language {} with {} replacements", best_lang.lang_id, max_replacements);
+        (best_lang.lang_native.to_owned(), Cow::Owned(best_translated_log))
     }
 }
 
-impl IntegrityChecker for EacParserSingle {
+impl<'a> IntegrityChecker for EacParserSingle<'a> {
     fn extract_checksum(&self) -> String {
         let captures = CHECKSUM.captures(&self.translated_log);
         match captures {
@@ -439,7 +473,7 @@ impl IntegrityChecker for EacParserSingle {
     }
 
     fn calculate_checksum(&self) -> String {
-        let checksum_stripped = CHECKSUM.replace_all(&self.log, "");
+        let checksum_stripped = CHECKSUM.replace_all(self.log, "");
         let utf16data: Vec<u16> = checksum_stripped
                                         .replace(['\r', '\n'], "")
                                         .encode_utf16()
@@ -464,20 +498,20 @@ impl IntegrityChecker for EacParserSingle {
     }
 }
 
-impl EacParserTrack {
-    fn new(is_range: bool, use_null_samples: Quartet, raw: String) -> Self {
+impl<'a> EacParserTrack<'a> {
+    fn new(is_range: bool, use_null_samples: Quartet, raw: &'a str) -> Self {
         EacParserTrack { is_range, use_null_samples, raw }
     }
 
     fn string_match(&self, regex: &Regex) -> String {
-        match regex.captures(&self.raw) {
+        match regex.captures(self.raw) {
             Some(val) => val.name("value").unwrap().as_str().trim().to_string(),
             None => String::default(),
         }
     }
 
     fn string_match_trim_crlf(&self, regex: &Regex) -> String {
-        match regex.captures(&self.raw) {
+        match regex.captures(self.raw) {
             Some(val) => val.name("value").unwrap().as_str().trim_matches(|c| c == '\n' || c == '\r').to_string(),
             None => String::default(),
         }
@@ -487,13 +521,13 @@ impl EacParserTrack {
     where
         T: FromStr,
         <T as FromStr>::Err: std::fmt::Debug, {
-        regex.captures(&self.raw).and_then(|val| {
+        regex.captures(self.raw).and_then(|val| {
             val.name("value").and_then(|v| v.as_str().trim().parse::<T>().ok())
         })
     }
 }
 
-impl TrackExtractor for EacParserTrack {
+impl<'a> TrackExtractor for EacParserTrack<'a> {
     fn extract_num(&self) -> u8 {
         if self.is_range { 0 } else { self.string_match(&TRACK_NUMBER).parse::<u8>().unwrap_or_default() }
     }
@@ -503,7 +537,7 @@ impl TrackExtractor for EacParserTrack {
     }
 
     fn extract_is_aborted(&self) -> bool {
-        let captures = COPY_ABORTED.captures(&self.raw);
+        let captures = COPY_ABORTED.captures(self.raw);
         captures.is_some()
     }
 
@@ -517,7 +551,7 @@ impl TrackExtractor for EacParserTrack {
     }
 
     fn extract_pregap_length(&self) -> Option<Time> {
-        let captures = PREGAP.captures(&self.raw);
+        let captures = PREGAP.captures(self.raw);
         captures.map(|captures| Time::from_h_mm_ss(captures.name("time").unwrap().as_str()))
     }
 
@@ -537,7 +571,7 @@ impl TrackExtractor for EacParserTrack {
     }
 
     fn extract_errors(&self) -> TrackError {
-        let captures_all = ERROR.captures_iter(&self.raw);
+        let captures_all = ERROR.captures_iter(self.raw);
         let mut read_errors: Vec<TrackErrorRange> = Vec::new();
         let mut jitter_errors: Vec<TrackErrorRange> = Vec::new();
 
@@ -569,17 +603,17 @@ impl TrackExtractor for EacParserTrack {
         let mut ars: Vec<AccurateRipUnit> = Vec::new();
 
         // AR enabled but not found in database
-        if AR_NO_DB.is_match(&self.raw) {
+        if AR_NO_DB.is_match(self.raw) {
             ars.push(AccurateRipUnit::new_eac_notfound());
             return ars;
         }
 
-        if let Some(ar_f_raw) = AR_FOUND.captures(&self.raw) {
+        if let Some(ar_f_raw) = AR_FOUND.captures(self.raw) {
             let version = ar_f_raw.name("version").unwrap().as_str().parse::<u8>().unwrap();
             let sign = ar_f_raw.name("sign").unwrap().as_str().to_owned();
             let matching = ar_f_raw.name("cm").unwrap().as_str().parse::<u32>().unwrap();
             ars.push(AccurateRipUnit::new_eac(version, sign, matching));
-        } else if let Some(ar_m_raw) = AR_MISMATCH.captures(&self.raw) {
+        } else if let Some(ar_m_raw) = AR_MISMATCH.captures(self.raw) {
             let version = ar_m_raw.name("version").unwrap().as_str().parse::<u8>().unwrap();
             let sign = ar_m_raw.name("sign").unwrap().as_str().to_owned();
             let offset_sign = ar_m_raw.name("off_sign").unwrap().as_str().to_owned();
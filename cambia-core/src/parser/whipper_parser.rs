@@ -1,5 +1,7 @@
 mod whipper_yaml;
 
+use std::borrow::Cow;
+
 use regex::Regex;
 use sha2::{Sha256, Digest};
 
@@ -21,8 +23,8 @@ pub struct WhipperParser {
     encoded_log: DecodedText,
 }
 
-struct WhipperParserSingle {
-    log: String,
+struct WhipperParserSingle<'a> {
+    log: &'a str,
     language: String,
     yaml: WhipperLogYaml,
 }
@@ -40,11 +42,11 @@ impl WhipperParser {
     }
 }
 
-impl WhipperParserSingle {
-    pub fn new(log: String) -> WhipperParserSingle {
-        let (language, _) = WhipperParserSingle::translate(log.clone());
+impl<'a> WhipperParserSingle<'a> {
+    pub fn new(log: &'a str) -> WhipperParserSingle<'a> {
+        let (language, _) = WhipperParserSingle::translate(log);
 
-        let yaml_sanitised = SANITISE_RELEASE.replace(&log, "${1}: \"${2}\"");
+        let yaml_sanitised = SANITISE_RELEASE.replace(log, "${1}: \"${2}\"");
         let yaml: WhipperLogYaml = serde_yaml::from_str(&yaml_sanitised).unwrap_or_default();
         tracing::debug!("Whipper YAML: {yaml:?}");
         
@@ -75,8 +77,12 @@ impl<'a> WhipperParserTrack<'a> {
 }
 
 impl ParserCombined for WhipperParser {
+    fn ripper(&self) -> Ripper {
+        Ripper::Whipper
+    }
+
     fn parse_combined(&self) -> ParsedLogCombined {
-        let parsed_logs: Vec<ParsedLog> = vec![WhipperParserSingle::new(self.encoded_log.text.trim().to_string()).parse()];
+        let parsed_logs: Vec<ParsedLog> = vec![WhipperParserSingle::new(self.encoded_log.text.trim()).parse()];
 
         ParsedLogCombined {
             parsed_logs,
@@ -91,9 +97,9 @@ impl TranslatorCombined for WhipperParser {
     }
 }
 
-impl Parser for WhipperParserSingle {}
+impl<'a> Parser for WhipperParserSingle<'a> {}
 
-impl Extractor for WhipperParserSingle {
+impl<'a> Extractor for WhipperParserSingle<'a> {
     fn extract_ripper(&self) -> Ripper {
         Ripper::Whipper
     }
@@ -196,13 +202,13 @@ impl Extractor for WhipperParserSingle {
     }
 }
 
-impl Translator for WhipperParserSingle {
-    fn translate(log: String) -> (String, String) {
-        (String::from("English"), log)
+impl<'a> Translator for WhipperParserSingle<'a> {
+    fn translate(log: &str) -> (String, Cow<'_, str>) {
+        (String::from("English"), Cow::Borrowed(log))
     }
 }
 
-impl IntegrityChecker for WhipperParserSingle {
+impl<'a> IntegrityChecker for WhipperParserSingle<'a> {
     fn extract_checksum(&self) -> String {
         if self.yaml.checksum == "INVALID" {
             return String::default();
@@ -212,7 +218,7 @@ impl IntegrityChecker for WhipperParserSingle {
 
     fn calculate_checksum(&self) -> String {
         // This DOES NOT consider CRLF
-        let checksum_stripped = CHECKSUM.replace_all(&self.log, "");
+        let checksum_stripped = CHECKSUM.replace_all(self.log, "");
         let mut hasher = Sha256::new();
         
         hasher.update(checksum_stripped.as_bytes());
@@ -1,6 +1,6 @@
 mod sha256custom;
 
-use std::{collections::{HashMap, HashSet}, iter::zip, str::FromStr};
+use std::{borrow::Cow, collections::{HashMap, HashSet}, iter::zip, str::FromStr};
 
 use regex::{Regex, RegexBuilder};
 use base64::{Engine as _, engine::GeneralPurpose, engine::general_purpose::PAD, alphabet::Alphabet};
@@ -59,9 +59,9 @@ pub struct XldParser {
     encoded_log: DecodedText,
 }
 
-struct XldParserSingle {
-    log: String,
-    translated_log: String,
+struct XldParserSingle<'a> {
+    log: &'a str,
+    translated_log: Cow<'a, str>,
     language: String,
 }
 
@@ -73,14 +73,14 @@ impl XldParser {
     }
 }
 
-struct XldParserTrack {
+struct XldParserTrack<'a> {
     is_range: bool,
-    raw: String,
+    raw: &'a str,
 }
 
-impl XldParserSingle {
-    pub fn new(log: String) -> XldParserSingle {
-        let (language, translated_log) = XldParserSingle::translate(log.clone());
+impl<'a> XldParserSingle<'a> {
+    pub fn new(log: &'a str) -> XldParserSingle<'a> {
+        let (language, translated_log) = XldParserSingle::translate(log);
         XldParserSingle {
             log,
             translated_log,
@@ -106,8 +106,12 @@ impl XldParserSingle {
 }
 
 impl ParserCombined for XldParser {
+    fn ripper(&self) -> Ripper {
+        Ripper::XLD
+    }
+
     fn parse_combined(&self) -> ParsedLogCombined {
-        let parsed_logs: Vec<ParsedLog> = vec![XldParserSingle::new(self.encoded_log.text.trim().to_string()).parse()];
+        let parsed_logs: Vec<ParsedLog> = vec![XldParserSingle::new(self.encoded_log.text.trim()).parse()];
 
         ParsedLogCombined {
             parsed_logs,
@@ -122,9 +126,9 @@ impl TranslatorCombined for XldParser {
     }
 }
 
-impl Parser for XldParserSingle {}
+impl<'a> Parser for XldParserSingle<'a> {}
 
-impl Extractor for XldParserSingle {
+impl<'a> Extractor for XldParserSingle<'a> {
     fn extract_ripper(&self) -> Ripper {
         Ripper::XLD
     }
@@ -318,7 +322,7 @@ impl Extractor for XldParserSingle {
                 }
 
                 if idx > 0 {
-                    tracks.push(XldParserTrack::new(is_range, self.translated_log[prev_start..start].trim().to_owned()).parse_track());
+                    tracks.push(XldParserTrack::new(is_range, self.translated_log[prev_start..start].trim()).parse_track());
                 }
 
                 prev_start = m.start();
@@ -328,22 +332,22 @@ impl Extractor for XldParserSingle {
         }
 
         if prev_start > 0 {
-            tracks.push(XldParserTrack::new(is_range, self.translated_log[prev_start..last_idx].trim().to_owned()).parse_track());
+            tracks.push(XldParserTrack::new(is_range, self.translated_log[prev_start..last_idx].trim()).parse_track());
         }
 
         tracks
     }
 }
 
-impl ParserTrack for XldParserTrack {}
+impl<'a> ParserTrack for XldParserTrack<'a> {}
 
-impl Translator for XldParserSingle {
-    fn translate(log: String) -> (String, String) {
-        (String::from("English"), log)
+impl<'a> Translator for XldParserSingle<'a> {
+    fn translate(log: &str) -> (String, Cow<'_, str>) {
+        (String::from("English"), Cow::Borrowed(log))
     }
 }
 
-impl IntegrityChecker for XldParserSingle {
+impl<'a> IntegrityChecker for XldParserSingle<'a> {
     fn extract_checksum(&self) -> String {
         let captures = CHECKSUM.captures(&self.translated_log);
         match captures {
@@ -353,7 +357,7 @@ impl IntegrityChecker for XldParserSingle {
     }
 
     fn calculate_checksum(&self) -> String {
-        let checksum_stripped = CHECKSUM.replace_all(&self.log, "");
+        let checksum_stripped = CHECKSUM.replace_all(self.log, "");
         let mut hasher = Sha256Custom::new([0x1D95E3A4, 0x06520EF5, 0x3A9CFB75, 0x6104BCAE, 0x09CEDA82, 0xBA55E60B, 0xEAEC16C6, 0xEB19AF15]);
         let mut utf8bytes = checksum_stripped.as_bytes().to_vec();
 
@@ -368,13 +372,13 @@ impl IntegrityChecker for XldParserSingle {
     }
 }
 
-impl XldParserTrack {
-    fn new(is_range: bool, raw: String) -> Self {
+impl<'a> XldParserTrack<'a> {
+    fn new(is_range: bool, raw: &'a str) -> Self {
         XldParserTrack { is_range, raw }
     }
 
     fn string_match(&self, regex: &Regex) -> String {
-        match regex.captures(&self.raw) {
+        match regex.captures(self.raw) {
             Some(val) => val.name("value").unwrap().as_str().trim().to_string(),
             None => String::default(),
         }
@@ -384,11 +388,11 @@ impl XldParserTrack {
     where
         T: FromStr,
         <T as FromStr>::Err: std::fmt::Debug, {
-        regex.captures(&self.raw).map(|val| val.name("value").unwrap().as_str().trim().parse::<T>().unwrap())
+        regex.captures(self.raw).map(|val| val.name("value").unwrap().as_str().trim().parse::<T>().unwrap())
     }
 }
 
-impl TrackExtractor for XldParserTrack {
+impl<'a> TrackExtractor for XldParserTrack<'a> {
     fn extract_num(&self) -> u8 {
         self.string_match(&TRACK_NUMBER).parse::<u8>().unwrap_or_default()
     }
@@ -407,7 +411,7 @@ impl TrackExtractor for XldParserTrack {
 
         filenames.push(first_file.clone());
 
-        let captures = FILENAME_MULTI.captures(&self.raw);
+        let captures = FILENAME_MULTI.captures(self.raw);
         if let Some(c) = captures {
             let value = c.name("value").unwrap().as_str().trim();
 
@@ -430,7 +434,7 @@ impl TrackExtractor for XldParserTrack {
     }
 
     fn extract_pregap_length(&self) -> Option<Time> {
-        let captures = PREGAP.captures(&self.raw);
+        let captures = PREGAP.captures(self.raw);
         captures.map(|captures| Time::from_mm_ss_cs(captures.name("time").unwrap().as_str()))
     }
 
@@ -444,7 +448,7 @@ impl TrackExtractor for XldParserTrack {
 
     // TODO: Feels kind of ugly
     fn extract_errors(&self) -> TrackError {
-        let captures_all = ERROR.captures_iter(&self.raw);
+        let captures_all = ERROR.captures_iter(self.raw);
 
         let (mut r_c, mut s_c, mut drf_c, mut drp_c, mut dup_c, mut dmg_c, mut inc_c) = (0_u32, 0_u32, 0_u32, 0_u32, 0_u32, 0_u32, 0_u32);
         let (mut jg_c, mut je_c, mut ja_c) = (0_u32, 0_u32, 0_u32);
@@ -475,7 +479,7 @@ impl TrackExtractor for XldParserTrack {
 
         // Damaged sector positions
         let mut dmg_r: Vec<TrackErrorRange> = Vec::new();
-        if let Some(c) = DAMAGED_SECTORS.captures(&self.raw) {
+        if let Some(c) = DAMAGED_SECTORS.captures(self.raw) {
             let positions = ERROR_TIME.captures_iter(c.get(0).unwrap().as_str());
 
             for position in positions {
@@ -491,7 +495,7 @@ impl TrackExtractor for XldParserTrack {
 
         // Suspicious positions
         let mut inc_r: Vec<TrackErrorRange> = Vec::new();
-        if let Some(c) = SUSPICIOUS_POSITIONS.captures(&self.raw) {
+        if let Some(c) = SUSPICIOUS_POSITIONS.captures(self.raw) {
             let positions = ERROR_TIME.captures_iter(c.get(0).unwrap().as_str());
 
             for position in positions {
@@ -511,7 +515,7 @@ impl TrackExtractor for XldParserTrack {
     fn extract_ar_info(&self) -> Vec<AccurateRipUnit> {
         let mut ars: Vec<AccurateRipUnit> = Vec::new();
         
-        if let Some(c) = AR_BLOCK.captures(&self.raw) {
+        if let Some(c) = AR_BLOCK.captures(self.raw) {
             let ar_raw = c.name("ar").unwrap().as_str();
             let ar_found = AR_FOUND.is_match(ar_raw);
             
@@ -0,0 +1,27 @@
+use crate::{evaluate::EvaluatorType, extract::Ripper};
+
+pub enum ParseEvent {
+    RipperDetected(Ripper),
+    // Combined logs report each section separately, sections may finish out of order
+    LogParsed { index: usize, total: usize },
+    EvaluatorFinished(EvaluatorType),
+}
+
+pub trait ParseObserver: Sync {
+    fn on_event(&self, event: ParseEvent);
+}
+
+impl<F> ParseObserver for F
+where
+    F: Fn(ParseEvent) + Sync,
+{
+    fn on_event(&self, event: ParseEvent) {
+        self(event)
+    }
+}
+
+pub struct NoopObserver;
+
+impl ParseObserver for NoopObserver {
+    fn on_event(&self, _event: ParseEvent) {}
+}
@@ -0,0 +1,158 @@
+use std::fmt::{self, Write};
+
+use serde::Serialize;
+
+use crate::accurip::{AccuripConfidence, AccuripReport};
+use crate::authenticity::Authenticity;
+use crate::evaluate::{EvaluationCombined, EvaluationUnitScope};
+use crate::extract::Quartet;
+use crate::parser::ParsedLog;
+use crate::response::CambiaResponse;
+
+// Canonical plain-text rendering, shared by the CLI's pretty output and the server's text format so the wording stays identical
+pub fn render_text(response: &CambiaResponse) -> String {
+    let mut out = String::new();
+    write_response(&mut out, response).expect("Writing to a String can't fail");
+    out
+}
+
+pub fn render_accurip(report: &AccuripReport) -> String {
+    let mut out = String::new();
+    write_accurip(&mut out, report).expect("Writing to a String can't fail");
+    out
+}
+
+pub fn scope_label(scope: &EvaluationUnitScope) -> String {
+    match scope {
+        EvaluationUnitScope::Release => String::from("Release"),
+        EvaluationUnitScope::Track(Some(num)) => format!("Track {}", num),
+        EvaluationUnitScope::Track(None) => String::from("Track"),
+    }
+}
+
+fn write_response(out: &mut String, response: &CambiaResponse) -> fmt::Result {
+    let performer = response.metadata.performer.as_deref().unwrap_or("Unknown Artist");
+    let title = response.metadata.title.as_deref().unwrap_or("Unknown Album");
+    writeln!(out, "{} - {}", performer, title)?;
+
+    for evaluation_combined in response.evaluation_combined.iter() {
+        writeln!(out, "{} score: {}", label(&evaluation_combined.evaluator), evaluation_combined.combined_score)?;
+    }
+
+    let total = response.parsed.parsed_logs.len();
+    for (idx, log) in response.parsed.parsed_logs.iter().enumerate() {
+        writeln!(out)?;
+        if total > 1 {
+            writeln!(out, "Log {} of {}", idx + 1, total)?;
+        }
+        write_log(out, log)?;
+        write_deductions(out, &response.evaluation_combined, idx)?;
+        if let Some(authenticity) = response.authenticity.get(idx) {
+            write_authenticity(out, authenticity)?;
+        }
+    }
+
+    if let Some(accurip) = response.accurip.as_ref() {
+        writeln!(out)?;
+        write_accurip(out, accurip)?;
+    }
+
+    Ok(())
+}
+
+fn write_log(out: &mut String, log: &ParsedLog) -> fmt::Result {
+    writeln!(out, "{} {}", label(&log.ripper), log.ripper_version)?;
+    match log.read_offset {
+        Some(offset) => writeln!(out, "Drive: {} (read offset {})", log.drive, offset)?,
+        None => writeln!(out, "Drive: {}", log.drive)?,
+    }
+
+    writeln!(out, "Settings")?;
+    let settings = [
+        ("Media type", label(&log.media_type)),
+        ("Read mode", label(&log.read_mode)),
+        ("Accurate stream", quartet(log.accurate_stream)),
+        ("Defeat audio cache", quartet(log.defeat_audio_cache)),
+        ("C2 pointers", quartet(log.use_c2)),
+        ("Test and copy", quartet(log.test_and_copy)),
+        ("Null samples in CRC", quartet(log.use_null_samples)),
+        ("Gap handling", label(&log.gap_handling)),
+    ];
+    for (name, value) in settings {
+        writeln!(out, "  {:<20} {}", name, value)?;
+    }
+    if let Some(compressor) = log.compressor.as_ref() {
+        writeln!(out, "  {:<20} {} {}", "Compressor", compressor.encoder, compressor.options)?;
+    }
+
+    let extracted = log.tracks.iter().filter(|track| !track.is_range).count();
+    if log.missing_tracks.is_empty() {
+        writeln!(out, "Tracks: {} extracted", extracted)
+    } else {
+        let missing: Vec<String> = log.missing_tracks.iter().map(u8::to_string).collect();
+        writeln!(out, "Tracks: {} extracted, missing {}", extracted, missing.join(", "))
+    }
+}
+
+fn write_deductions(out: &mut String, evaluation_combined: &[EvaluationCombined], log_idx: usize) -> fmt::Result {
+    for evaluation_combined in evaluation_combined.iter() {
+        let Some(evaluation) = evaluation_combined.evaluations.get(log_idx) else {
+            continue;
+        };
+
+        writeln!(out, "Deductions ({}, log score {})", label(&evaluation_combined.evaluator), evaluation.score)?;
+        if evaluation.evaluation_units.is_empty() {
+            writeln!(out, "  None")?;
+        }
+        for unit in evaluation.evaluation_units.iter() {
+            writeln!(out, "  -{:<4} {:<9} {}", unit.unit_score, scope_label(&unit.data.scope), unit.data.message)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_authenticity(out: &mut String, authenticity: &Authenticity) -> fmt::Result {
+    if authenticity.signals.is_empty() {
+        return Ok(());
+    }
+
+    writeln!(out, "Authenticity{}", if authenticity.likely_virtual { " (likely ripped from a virtual drive)" } else { "" })?;
+    for signal in authenticity.signals.iter() {
+        writeln!(out, "  {}", signal.detail)?;
+    }
+    Ok(())
+}
+
+fn write_accurip(out: &mut String, report: &AccuripReport) -> fmt::Result {
+    writeln!(out, "CUETools report{}", report.version.as_ref().map(|v| format!(" ({})", v)).unwrap_or_default())?;
+    if let Some(ctdb) = report.ctdb {
+        writeln!(out, "  CTDB: {}", confidence(ctdb))?;
+    }
+    for track in report.tracks.iter() {
+        let accuraterip = track.accuraterip.map(confidence).unwrap_or_else(|| String::from("-"));
+        writeln!(out, "  Track {:<3} AccurateRip: {}", track.num, accuraterip)?;
+    }
+    Ok(())
+}
+
+fn confidence(confidence: AccuripConfidence) -> String {
+    let status = if confidence.accurate { "accurate" } else { "no match" };
+    format!("{} ({}/{})", status, confidence.matching, confidence.total)
+}
+
+fn quartet(value: Quartet) -> String {
+    match value {
+        Quartet::True => String::from("Yes"),
+        Quartet::False => String::from("No"),
+        Quartet::Unknown => String::from("Unknown"),
+        Quartet::Unsupported => String::from("Unsupported"),
+    }
+}
+
+// Enum names as they appear in the JSON output
+fn label<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_owned))
+        .unwrap_or_default()
+}
@@ -1,18 +1,64 @@
 use serde::{Serialize, Deserialize};
 use ts_rs::TS;
+use xxhash_rust::xxh3::xxh3_64;
 
-use crate::{parser::ParsedLogCombined, evaluate::EvaluationCombined};
+use crate::{accurip::AccuripReport, authenticity::Authenticity, metadata::ReleaseMetadata, parser::ParsedLogCombined, evaluate::{EvaluationCombined, EvaluatorType}, extract::Ripper};
+
+#[derive(Serialize, Deserialize, TS, Debug, Clone, Copy, Default, PartialEq, Eq, strum_macros::EnumString, strum_macros::Display)]
+#[ts(export)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+pub enum IdHashAlgorithm {
+    #[default]
+    Xxh3,
+    Blake3,
+}
+
+impl IdHashAlgorithm {
+    pub fn hash(&self, log_raw: &[u8]) -> Vec<u8> {
+        match self {
+            IdHashAlgorithm::Xxh3 => xxh3_64(log_raw).to_be_bytes().to_vec(),
+            IdHashAlgorithm::Blake3 => blake3::hash(log_raw).as_bytes().to_vec(),
+        }
+    }
+}
 
 #[derive(Serialize, Deserialize, TS)]
 #[ts(export)]
 pub struct CambiaResponse {
     pub id: Vec<u8>,
+    // Empty when the ID was supplied by the caller
+    pub id_hash: Option<IdHashAlgorithm>,
     pub parsed: ParsedLogCombined,
+    pub metadata: ReleaseMetadata,
+    // One entry per log in the combined log
+    pub authenticity: Vec<Authenticity>,
+    // Attached by the caller from a CUETools report next to the log
+    pub accurip: Option<AccuripReport>,
     pub evaluation_combined: Vec<EvaluationCombined>,
+    pub diagnostics: Option<Diagnostics>,
+}
+
+#[derive(Serialize, Deserialize, TS)]
+#[ts(export)]
+pub struct Diagnostics {
+    pub duration_ms: f64,
+    pub encoding: String,
+    pub log_size: usize,
+    pub parser: Ripper,
+    pub evaluators: Vec<EvaluatorType>,
 }
 
 impl CambiaResponse {
-    pub fn new(id: Vec<u8>, parsed: ParsedLogCombined, evaluation_combined: Vec<EvaluationCombined>) -> Self {
-        Self { id, parsed, evaluation_combined }
+    pub fn new(id: Vec<u8>, id_hash: Option<IdHashAlgorithm>, parsed: ParsedLogCombined, evaluation_combined: Vec<EvaluationCombined>) -> Self {
+        let metadata = ReleaseMetadata::from_parsed(&parsed);
+        let authenticity = Authenticity::from_parsed(&parsed);
+        Self { id, id_hash, parsed, metadata, authenticity, accurip: None, evaluation_combined, diagnostics: None }
+    }
+
+    pub fn score(&self, evaluator: EvaluatorType) -> Option<i32> {
+        self.evaluation_combined.iter()
+            .find(|evaluation| evaluation.evaluator == evaluator)
+            .and_then(|evaluation| evaluation.combined_score.parse::<i32>().ok())
     }
 }
\ No newline at end of file
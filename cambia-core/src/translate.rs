@@ -1,6 +1,8 @@
+use std::borrow::Cow;
+
 pub trait Translator {
-    // TODO: Should use Cow for Translator API
-    fn translate(log: String) -> (String, String);
+    // Borrows the log when no translation is needed
+    fn translate(log: &str) -> (String, Cow<'_, str>);
 }
 
 pub trait TranslatorCombined {
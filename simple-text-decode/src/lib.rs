@@ -87,6 +87,10 @@ impl DecodedText {
     }
 }
 
+pub fn has_bom(raw: &[u8]) -> bool {
+    !matches!(Bom::from(raw), Bom::Null)
+}
+
 impl fmt::Display for DecodingError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "Decoding Error")
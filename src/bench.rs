@@ -0,0 +1,170 @@
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use cambia_core::batch::{parse_many, BatchEntry, BatchOptions};
+use cambia_core::error::CambiaErrorKind;
+use cambia_core::handler::ParseOptions;
+use crate::util::{is_log_file, scan_files, LogFile};
+
+#[cfg(feature = "bench")]
+pub mod alloc {
+    use std::alloc::{GlobalAlloc, Layout, System};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    pub struct CountingAllocator;
+
+    static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+    static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+    static CURRENT_BYTES: AtomicUsize = AtomicUsize::new(0);
+    static PEAK_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+                ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+                let current = CURRENT_BYTES.fetch_add(layout.size(), Ordering::Relaxed) + layout.size();
+                PEAK_BYTES.fetch_max(current, Ordering::Relaxed);
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            CURRENT_BYTES.fetch_sub(layout.size(), Ordering::Relaxed);
+        }
+    }
+
+    pub struct AllocSnapshot {
+        pub allocations: usize,
+        pub allocated_bytes: usize,
+    }
+
+    pub fn snapshot() -> AllocSnapshot {
+        AllocSnapshot {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            allocated_bytes: ALLOCATED_BYTES.load(Ordering::Relaxed),
+        }
+    }
+
+    // Peak is tracked from this point on
+    pub fn reset_peak() {
+        PEAK_BYTES.store(CURRENT_BYTES.load(Ordering::Relaxed), Ordering::Relaxed);
+    }
+
+    pub fn peak_bytes() -> usize {
+        PEAK_BYTES.load(Ordering::Relaxed)
+    }
+}
+
+pub fn run_bench(dir: &Path, iterations: u64, scan_threads: usize) {
+    let logs: Vec<LogFile> = collect_logs(dir, scan_threads);
+    if logs.is_empty() {
+        println!("No log files found in {}", dir.display());
+        return;
+    }
+
+    let options = BatchOptions {
+        parse: ParseOptions { diagnostics: true, ..Default::default() },
+        ..Default::default()
+    };
+
+    // The warm-up pass builds lazy tables and sorts out the logs that don't parse, so they can't skew the throughput
+    let mut not_rip_logs: usize = 0;
+    let mut failed: usize = 0;
+    let warm_up = parse_many(&logs, &options);
+    let logs: Vec<LogFile> = logs.into_iter().zip(warm_up).filter_map(|(log, entry)| match entry {
+        BatchEntry::Parsed(_) => Some(log),
+        BatchEntry::Failed(e) if e.kind == CambiaErrorKind::NotRipLog => {
+            not_rip_logs += 1;
+            None
+        },
+        _ => {
+            failed += 1;
+            None
+        },
+    }).collect();
+
+    println!("Skipped: {} not rip logs, {} failed to parse", not_rip_logs, failed);
+    if logs.is_empty() {
+        println!("No parseable logs found in {}", dir.display());
+        return;
+    }
+
+    println!("Benchmarking {} logs over {} iterations", logs.len(), iterations);
+
+    let mut latencies: BTreeMap<String, Vec<f64>> = BTreeMap::new();
+    let mut parsed: usize = 0;
+    let mut elapsed = Duration::ZERO;
+
+    #[cfg(feature = "bench")]
+    let alloc_before = {
+        alloc::reset_peak();
+        alloc::snapshot()
+    };
+
+    for _ in 0..iterations {
+        let started = Instant::now();
+        let entries = parse_many(&logs, &options);
+        elapsed += started.elapsed();
+
+        for entry in entries {
+            let BatchEntry::Parsed(response) = entry else {
+                continue;
+            };
+
+            parsed += 1;
+            if let Some(diagnostics) = response.diagnostics {
+                let ripper = serde_json::to_value(&diagnostics.parser)
+                    .ok()
+                    .and_then(|v| v.as_str().map(str::to_owned))
+                    .unwrap_or_default();
+                latencies.entry(ripper).or_default().push(diagnostics.duration_ms);
+            }
+        }
+    }
+
+    println!("Parsed: {}", parsed);
+    println!("Throughput: {:.1} logs/sec", parsed as f64 / elapsed.as_secs_f64());
+
+    for (ripper, durations) in latencies.iter_mut() {
+        durations.sort_by(|a, b| a.total_cmp(b));
+        println!(
+            "{:<24} n={:<8} p50={:.3}ms p99={:.3}ms",
+            ripper, durations.len(), percentile(durations, 0.5), percentile(durations, 0.99)
+        );
+    }
+
+    #[cfg(feature = "bench")]
+    {
+        let alloc_after = alloc::snapshot();
+        let runs = logs.len() as f64 * iterations as f64;
+        println!(
+            "Allocations: {:.1} per log, {:.1} KiB per log, peak {:.1} MiB",
+            (alloc_after.allocations - alloc_before.allocations) as f64 / runs,
+            (alloc_after.allocated_bytes - alloc_before.allocated_bytes) as f64 / runs / 1024.0,
+            alloc::peak_bytes() as f64 / 1024.0 / 1024.0,
+        );
+    }
+    #[cfg(not(feature = "bench"))]
+    println!("Allocation stats require building with the bench feature");
+}
+
+fn collect_logs(dir: &Path, scan_threads: usize) -> Vec<LogFile> {
+    scan_files(dir, scan_threads, None)
+        .filter(|path| is_log_file(path))
+        .filter_map(|path: PathBuf| match LogFile::open(&path) {
+            Ok(raw) => Some(raw),
+            Err(e) => {
+                tracing::error!("Error reading {}: {}", path.display(), e);
+                None
+            },
+        })
+        .collect()
+}
+
+fn percentile(sorted: &[f64], q: f64) -> f64 {
+    let idx = ((sorted.len() - 1) as f64 * q).round() as usize;
+    sorted[idx]
+}
@@ -0,0 +1,59 @@
+use std::fs::File;
+use std::path::Path;
+use cambia_core::evaluate::{EvaluationUnitClass, EvaluationUnitField, EvaluatorType};
+use cambia_core::render::scope_label;
+use cambia_core::response::CambiaResponse;
+use serde::Serialize;
+
+#[derive(Serialize)]
+struct DeductionRow<'a> {
+    path: &'a Path,
+    evaluator: EvaluatorType,
+    // Index of the log within a combined log file
+    log: usize,
+    scope: String,
+    code: &'a str,
+    field: &'a EvaluationUnitField,
+    points: &'a str,
+    // Whether the deduction counts toward the combined score
+    applied: bool,
+    class: &'a EvaluationUnitClass,
+    message: &'a str,
+}
+
+// One row per deduction across every log in the batch
+pub struct DeductionsCsv {
+    writer: csv::Writer<File>,
+}
+
+impl DeductionsCsv {
+    pub fn create(path: &Path) -> csv::Result<Self> {
+        Ok(Self { writer: csv::Writer::from_path(path)? })
+    }
+
+    pub fn write_response(&mut self, path: &Path, response: &CambiaResponse) -> csv::Result<()> {
+        for evaluation_combined in response.evaluation_combined.iter() {
+            for (log, evaluation) in evaluation_combined.evaluations.iter().enumerate() {
+                for unit in evaluation.evaluation_units.iter() {
+                    self.writer.serialize(DeductionRow {
+                        path,
+                        evaluator: evaluation_combined.evaluator,
+                        log,
+                        scope: scope_label(&unit.data.scope),
+                        code: &unit.data.code,
+                        field: &unit.data.field,
+                        points: &unit.unit_score,
+                        applied: unit.applied,
+                        class: &unit.data.class,
+                        message: &unit.data.message,
+                    })?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+}
@@ -1,5 +1,9 @@
 use std::path::PathBuf;
-use clap::Parser;
+use std::time::SystemTime;
+use clap::{Parser, Subcommand};
+use cambia_core::evaluate::gazelle_evaluate::ops_evaluate::OpsProfile;
+use cambia_core::handler::ParseOptions;
+use cambia_core::response::IdHashAlgorithm;
 #[cfg(feature = "server")]
 use figlet_rs::FIGfont;
 #[cfg(feature = "server")]
@@ -10,12 +14,20 @@ use crate::util::parse_file;
 mod server;
 mod util;
 mod consts;
+mod bench;
+mod export;
+
+#[cfg(feature = "bench")]
+#[global_allocator]
+static ALLOC: bench::alloc::CountingAllocator = bench::alloc::CountingAllocator;
 
 /// Program to parse log files generated by various CD ripping software
 #[derive(Parser, Clone, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct Args {
-    /// Path to the log file, ignores server mode arguments if present
+    #[command(subcommand)]
+    command: Option<Command>,
+    /// Path to the log file or a directory of log files, ignores server mode arguments if present
     #[arg(short, long)]
     path: Option<String>,
     /// Run the server and the web interface
@@ -26,12 +38,69 @@ pub struct Args {
     #[arg(long, env = "CAMBIA_PORT", value_parser = crate::server::port_in_range, default_value = crate::consts::DEFAULT_PORT)]
     #[cfg(feature = "server")]
     pub port: String,
+    /// Serve a directory of logs for browsing at /batch in the web interface
+    #[arg(long, env = "CAMBIA_SERVE_DIR")]
+    #[cfg(feature = "server")]
+    pub serve_dir: Option<PathBuf>,
     /// Set the log level
     #[arg(long, env = "CAMBIA_TRACING", default_value = "info")]
     pub tracing: String,
     /// Save the uploaded logs to a directory
     #[arg(long, env = "CAMBIA_SAVE_LOGS")]
     pub save_logs: Option<PathBuf>,
+    /// Skip logs with identical contents when parsing a directory
+    #[arg(long)]
+    pub dedup: bool,
+    /// Hash used for the log ID when one isn't supplied
+    #[arg(long, env = "CAMBIA_ID_HASH", default_value_t = IdHashAlgorithm::Xxh3)]
+    pub id_hash: IdHashAlgorithm,
+    /// Include timing and detection diagnostics in the response
+    #[arg(long, env = "CAMBIA_DIAGNOSTICS")]
+    pub diagnostics: bool,
+    /// Write every deduction in the batch to a CSV file
+    #[arg(long)]
+    pub deductions_csv: Option<PathBuf>,
+    /// Points deducted by OPS when null samples aren't used in CRC calculations, 0 makes it informational
+    #[arg(long, env = "CAMBIA_NULL_SAMPLES_DEDUCTION", default_value_t = 5)]
+    pub null_samples_deduction: u32,
+    /// Only list logs that failed to parse, grouped by failure reason
+    #[arg(long)]
+    pub only_errors: bool,
+    /// Only report the highest scoring log for each disc when parsing a directory, listing the others as alternatives
+    #[arg(long)]
+    pub best_per_album: bool,
+    /// Only parse files in a directory modified within a duration (7d, 12h) or after a UTC timestamp (2024-01-31 or 2024-01-31 18:00:00)
+    #[arg(long, value_parser = crate::util::parse_since)]
+    pub since: Option<SystemTime>,
+    /// Print a human-readable summary instead of JSON
+    #[arg(long)]
+    pub pretty: bool,
+    /// Number of threads reading directories concurrently when scanning a directory (CLI, serve dir and bench), 1 scans serially
+    #[arg(long, env = "CAMBIA_SCAN_THREADS", default_value_t = 8)]
+    pub scan_threads: usize,
+}
+
+#[derive(Subcommand, Clone, Debug)]
+enum Command {
+    /// Repeatedly parse a directory of logs and report throughput and latency
+    Bench {
+        /// Directory containing the log corpus
+        dir: PathBuf,
+        /// Number of timed passes over the corpus
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..))]
+        iterations: u64,
+    },
+}
+
+impl Args {
+    pub fn parse_options(&self) -> ParseOptions {
+        ParseOptions {
+            id_hash: self.id_hash,
+            diagnostics: self.diagnostics,
+            interner: None,
+            ops_profile: OpsProfile { null_samples_deduction: self.null_samples_deduction },
+        }
+    }
 }
 
 #[tokio::main]
@@ -44,6 +113,11 @@ pub async fn main() {
         tracing::info!("Log saving is enabled");
     }
 
+    if let Some(Command::Bench { dir, iterations }) = &args.command {
+        bench::run_bench(dir, *iterations, args.scan_threads);
+        return
+    }
+
     run(args).await;
 }
 
@@ -74,7 +148,12 @@ fn init_logging(tracing: &str) {
         _ => tracing::Level::INFO,
     };
 
-    tracing_subscriber::fmt()
-        .with_max_level(tracing_level)
-        .init();
+    let subscriber = tracing_subscriber::fmt()
+        .with_max_level(tracing_level);
+
+    // Span close events carry the time spent in each parser and evaluator phase
+    #[cfg(feature = "instrument")]
+    let subscriber = subscriber.with_span_events(tracing_subscriber::fmt::format::FmtSpan::CLOSE);
+
+    subscriber.init();
 }
@@ -1,5 +1,6 @@
-use std::{net::SocketAddr, ops::ControlFlow};
+use std::{net::SocketAddr, ops::ControlFlow, sync::Arc};
 use std::ops::RangeInclusive;
+use std::path::{Component, Path};
 use axum::{async_trait, body::{Body, Bytes}, extract::{
     connect_info::ConnectInfo, ws::{Message, WebSocket, WebSocketUpgrade}, FromRequestParts, Query
 }, http::{header, StatusCode, Uri}, response::{IntoResponse, Response}, routing::{get, post}, Extension, Json, Router};
@@ -13,10 +14,11 @@ use futures::{sink::SinkExt, stream::StreamExt};
 use tower_http::trace::{DefaultMakeSpan, TraceLayer};
 use axum_client_ip::{InsecureClientIp, SecureClientIp, SecureClientIpSource};
 use cambia_core::error::CambiaError;
-use cambia_core::handler::{parse_log_bytes, translate_log_bytes};
+use cambia_core::handler::{parse_log_bytes_async, translate_log_bytes_async};
+use cambia_core::render::render_text;
 use cambia_core::response::CambiaResponse;
 use crate::Args;
-use crate::util::save_rip_log;
+use crate::util::{is_log_file, save_rip_log, scan_files};
 
 static INDEX_HTML: &str = "index.html";
 
@@ -27,6 +29,8 @@ struct Static;
 enum Format {
     Json,
     MsgPack,
+    // Only meaningful for parse results, anything else is sent as JSON
+    Text,
 }
 
 impl Format {
@@ -35,7 +39,7 @@ impl Format {
         T: Serialize,
     {
         match self {
-            Format::Json => Json(data).into_response(),
+            Format::Json | Format::Text => Json(data).into_response(),
             Format::MsgPack => MsgPackRaw(data).into_response()
         }
     }
@@ -58,14 +62,19 @@ where
             Err(_) => return Ok(Self::Json),
         };
 
-        if query.fmt == "msgpack" {
-            Ok(Self::MsgPack)
-        } else {
-            Ok(Self::Json)
+        match query.fmt.as_str() {
+            "msgpack" => Ok(Self::MsgPack),
+            "text" => Ok(Self::Text),
+            _ => Ok(Self::Json),
         }
     }
 }
 
+#[derive(Deserialize)]
+struct BatchLogQuery {
+    path: String,
+}
+
 // TODO: Check for security implications
 pub struct CambiaServer {
     args: Args
@@ -82,6 +91,8 @@ impl CambiaServer {
         let single_upload = Router::new()
             .route("/v1/upload", post(Self::upload_log))
             .route("/v1/translate", post(Self::translate_log))
+            .route("/v1/batch", get(Self::list_batch))
+            .route("/v1/batch/log", get(Self::batch_log))
             .layer(CorsLayer::permissive())
             .layer(CompressionLayer::new().gzip(true).no_br().no_zstd());
 
@@ -166,7 +177,7 @@ impl CambiaServer {
             let mut cnt = 0;
             while let Some(Ok(msg)) = receiver.next().await {
                 cnt += 1;
-                let processed = Self::process_message(&args, msg, who);
+                let processed = Self::process_message(&args, msg, who).await;
                 if processed.is_break() {
                     break;
                 } else if let ControlFlow::Continue(val) = processed {
@@ -190,10 +201,10 @@ impl CambiaServer {
         tracing::trace!("Websocket context {} destroyed", who);
     }
 
-    fn process_message(args: &Args, msg: Message, who: SocketAddr) -> ControlFlow<(), Vec<u8>> {
+    async fn process_message(args: &Args, msg: Message, who: SocketAddr) -> ControlFlow<(), Vec<u8>> {
         match msg {
             Message::Binary(d) => {
-                let enc: Vec<u8> = match Self::parse_ws_request(args, d) {
+                let enc: Vec<u8> = match Self::parse_ws_request(args, d).await {
                     Ok(res) => rmp_serde::encode::to_vec_named(&res).unwrap(),
                     Err(e) => rmp_serde::encode::to_vec_named(&e).unwrap(),
                 };
@@ -215,14 +226,14 @@ impl CambiaServer {
         ControlFlow::Continue(Vec::new())
     }
 
-    fn parse_ws_request(args: &Args, mut ws_body: Vec<u8>) -> Result<CambiaResponse, CambiaError> {
+    async fn parse_ws_request(args: &Args, mut ws_body: Vec<u8>) -> Result<CambiaResponse, CambiaError> {
         // xxH64 is 8 bytes
         if ws_body.len() < 8 {
             return Err(CambiaError::new_anon("WS message length too small"));
         }
 
-        let log_bytes = ws_body.split_off(8);
-        let res = parse_log_bytes(ws_body, &log_bytes);
+        let log_bytes: Arc<[u8]> = ws_body.split_off(8).into();
+        let res = parse_log_bytes_async(ws_body, log_bytes.clone(), args.parse_options()).await;
 
         if let Some(save_logs) = args.save_logs.clone() {
             if let Ok(ref res) = res {
@@ -245,27 +256,77 @@ impl CambiaServer {
             .unwrap();
     }
 
-    async fn upload_log(fmt: Format, bytes: Bytes) -> impl IntoResponse {
-        let bytes_vec = bytes.to_vec();
-        match parse_log_bytes(Vec::new(), &bytes_vec) {
+    async fn upload_log(Extension(args): Extension<Args>, fmt: Format, bytes: Bytes) -> impl IntoResponse {
+        match parse_log_bytes_async(Vec::new(), bytes, args.parse_options()).await {
             Ok(parsed) => {
                 tracing::debug!("{}", serde_json::to_string(&parsed).unwrap());
-                (StatusCode::OK, fmt.render(parsed))
+                match fmt {
+                    Format::Text => (StatusCode::OK, render_text(&parsed).into_response()),
+                    _ => (StatusCode::OK, fmt.render(parsed)),
+                }
             },
             Err(e) => (StatusCode::BAD_REQUEST, e.to_string().into_response()),
         }
     }
 
+    // The web interface fetches each listed log and parses it like a dropped file
+    async fn list_batch(Extension(args): Extension<Args>) -> impl IntoResponse {
+        let Some(root) = args.serve_dir else {
+            return Self::not_found().await.into_response();
+        };
+
+        match tokio::task::spawn_blocking(move || batch_paths(&root, args.scan_threads)).await {
+            Ok(paths) => Json(paths).into_response(),
+            Err(e) => (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response(),
+        }
+    }
+
+    async fn batch_log(Extension(args): Extension<Args>, Query(query): Query<BatchLogQuery>) -> impl IntoResponse {
+        let Some(root) = args.serve_dir else {
+            return Self::not_found().await.into_response();
+        };
+
+        let relative = Path::new(&query.path);
+        if !is_log_file(relative) || !relative.components().all(|c| matches!(c, Component::Normal(_))) {
+            return (StatusCode::BAD_REQUEST, "Invalid log path").into_response();
+        }
+
+        // Symlinks are resolved first so they can't point outside the served directory
+        let path = match (tokio::fs::canonicalize(&root).await, tokio::fs::canonicalize(root.join(relative)).await) {
+            (Ok(root), Ok(path)) if path.starts_with(&root) => path,
+            _ => return Self::not_found().await.into_response(),
+        };
+
+        match tokio::fs::read(&path).await {
+            Ok(raw) => ([(header::CONTENT_TYPE, "application/octet-stream")], raw).into_response(),
+            Err(e) => {
+                tracing::error!("Error reading {}: {}", path.display(), e);
+                Self::not_found().await.into_response()
+            },
+        }
+    }
+
     async fn translate_log(bytes: Bytes) -> impl IntoResponse {
         let bytes_vec = bytes.to_vec();
 
-        match translate_log_bytes(bytes_vec) {
+        match translate_log_bytes_async(bytes_vec).await {
             Ok(parsed) => (StatusCode::OK, parsed.into_response()),
             Err(e) => (StatusCode::BAD_REQUEST, e.to_string().into_response()),
         }
     }
 }
 
+// Relative paths with forward slashes, so they can be passed straight back to /v1/batch/log
+fn batch_paths(root: &Path, scan_threads: usize) -> Vec<String> {
+    scan_files(root, scan_threads, None)
+        .filter(|path| is_log_file(path))
+        .filter_map(|path| {
+            let relative = path.strip_prefix(root).ok()?;
+            Some(relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/"))
+        })
+        .collect()
+}
+
 pub fn port_in_range(s: &str) -> Result<String, String> {
     const PORT_RANGE: RangeInclusive<usize> = 1..=65535;
 
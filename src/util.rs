@@ -1,32 +1,481 @@
-use std::fs::OpenOptions;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs::File;
 use std::io::Read;
-use std::path::PathBuf;
-use cambia_core::handler::parse_log_bytes;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+use memmap2::Mmap;
+use serde::Serialize;
+use jwalk::{DirEntry, Parallelism, WalkDir};
+use cambia_core::accurip::AccuripReport;
+use cambia_core::batch::{best_per_disc, parse_many_from, BatchEntry, BatchOptions, CancellationToken};
+use cambia_core::error::{CambiaError, CambiaErrorKind};
+use cambia_core::evaluate::EvaluatorType;
+use cambia_core::handler::parse_log_bytes_with_options;
+use cambia_core::metadata::ReleaseMetadata;
+use cambia_core::render::{render_accurip, render_text};
+use cambia_core::response::CambiaResponse;
 use crate::Args;
+use crate::export::DeductionsCsv;
+
+pub enum LogFile {
+	Mapped(Mmap),
+	Read(Vec<u8>),
+}
+
+impl LogFile {
+	pub fn open(path: &Path) -> std::io::Result<LogFile> {
+		let mut fh = File::open(path)?;
+
+		// Mapping zero-length files fails on some platforms
+		if fh.metadata()?.len() > 0 {
+			// SAFETY: The map is read-only, logs being modified mid-scan is not something we guard against
+			if let Ok(mmap) = unsafe { Mmap::map(&fh) } {
+				return Ok(LogFile::Mapped(mmap));
+			}
+		}
+
+		let mut raw: Vec<u8> = Vec::new();
+		fh.read_to_end(&mut raw)?;
+		Ok(LogFile::Read(raw))
+	}
+}
+
+impl AsRef<[u8]> for LogFile {
+	fn as_ref(&self) -> &[u8] {
+		match self {
+			LogFile::Mapped(mmap) => mmap,
+			LogFile::Read(raw) => raw,
+		}
+	}
+}
+
+#[derive(Serialize)]
+struct DirEntryResult<'a> {
+	path: &'a Path,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	response: Option<CambiaResponse>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	error: Option<CambiaError>,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	duplicate_of: Option<&'a Path>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	alternatives: Vec<Alternative<'a>>,
+}
+
+#[derive(Serialize)]
+struct Alternative<'a> {
+	path: &'a Path,
+	score: Option<i32>,
+}
+
+// CUETools reports in folders without any rip log
+#[derive(Serialize)]
+struct AccuripEntry<'a> {
+	path: &'a Path,
+	accurip: AccuripReport,
+}
+
+// .log files that aren't rip logs, reported together instead of as one error each
+#[derive(Serialize)]
+struct SkippedFiles<'a> {
+	skipped: &'static str,
+	paths: Vec<&'a Path>,
+}
+
+#[derive(Serialize)]
+struct ErrorGroup<'a> {
+	kind: CambiaErrorKind,
+	message: String,
+	logs: Vec<FailedLog<'a>>,
+}
+
+#[derive(Serialize)]
+struct FailedLog<'a> {
+	path: &'a Path,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	offset: Option<usize>,
+}
 
 pub fn parse_file(filepath: &str, args: Args) {
-	let mut raw: Vec<u8> = Vec::new();
+	let path = Path::new(filepath);
 
-	let mut fh = OpenOptions::new().read(true).open(filepath).expect(
-		"Could not open file",
-	);
+	if path.is_dir() {
+		return parse_dir(path, args);
+	}
 
-	fh.read_to_end(&mut raw).expect(
+	if is_accurip_file(path) {
+		if !args.only_errors {
+			print_accurip(path, args.pretty);
+		}
+		return
+	}
+
+	let raw = LogFile::open(path).expect(
 		"Could not read file"
 	);
 
-	let parsed = match parse_log_bytes(Vec::new(), &raw) {
+	let mut parsed = match parse_log_bytes_with_options(Vec::new(), raw.as_ref(), &args.parse_options()) {
 		Ok(parsed) => parsed,
-		Err(_) => return,
+		Err(e) => {
+			if args.only_errors {
+				print_error_groups(vec![(path, e)], args.pretty);
+			}
+			return
+		},
 	};
 
-	if let Ok(parsed) = parse_log_bytes(Vec::new(), &raw) {
-		println!("{}", serde_json::to_string(&parsed).unwrap());
+	if !args.only_errors {
+		let mut siblings = SiblingIndex::default();
+		merge_sibling_cue(&mut siblings, path, &mut parsed);
+		merge_sibling_accurip(&mut siblings, path, &mut parsed);
+		if args.pretty {
+			print!("{}", render_text(&parsed));
+		} else {
+			println!("{}", serde_json::to_string(&parsed).unwrap());
+		}
+	}
+
+	if let Some(mut deductions) = open_deductions_csv(&args) {
+		write_deductions(&mut deductions, path, &parsed);
+		finish_deductions(&mut deductions);
 	}
 
 	if let Some(save_logs) = args.save_logs {
-		save_rip_log(save_logs, &parsed.id, &raw);
+		save_rip_log(save_logs, &parsed.id, raw.as_ref());
+	}
+}
+
+fn parse_dir(root_path: &Path, args: Args) {
+	let mut paths: Vec<PathBuf> = Vec::new();
+	let mut accurips: Vec<PathBuf> = Vec::new();
+	let cancel = cancel_on_ctrl_c();
+
+	for path in scan_files(root_path, args.scan_threads, args.since) {
+		if cancel.is_cancelled() {
+			break;
+		}
+
+		if is_log_file(&path) {
+			paths.push(path);
+		} else if is_accurip_file(&path) {
+			accurips.push(path);
+		}
+	}
+
+	let options = BatchOptions { dedup: args.dedup, parse: args.parse_options(), cancel: cancel.clone() };
+	let load = |path: &PathBuf| LogFile::open(path).map_err(|e| CambiaError::new_anon(&format!("Could not read file: {}", e)).with_kind(CambiaErrorKind::Io));
+
+	// Logs are written out as soon as they're parsed, so the raw bytes don't outlive their worker
+	let entries = parse_many_from(&paths, load, &options, |_, entry, raw| {
+		if let (Some(save_logs), BatchEntry::Parsed(parsed)) = (args.save_logs.clone(), entry) {
+			save_rip_log(save_logs, &parsed.id, raw);
+		}
+	});
+	let mut cancelled: usize = 0;
+	let mut deductions = open_deductions_csv(&args);
+	let mut failures: Vec<(&Path, CambiaError)> = Vec::new();
+	let mut skipped: Vec<&Path> = Vec::new();
+	let mut attached_accurips: HashSet<PathBuf> = HashSet::new();
+	let mut siblings = SiblingIndex::default();
+
+	// Alternatives are scored before the entries are consumed, every other parsed log of the disc is left out
+	let mut alternatives: HashMap<usize, Vec<Alternative>> = HashMap::new();
+	if args.best_per_album {
+		for group in best_per_disc(&entries) {
+			let scored = group.alternatives.into_iter().map(|idx| Alternative {
+				path: paths[idx].as_path(),
+				score: match &entries[idx] {
+					BatchEntry::Parsed(response) => response.score(EvaluatorType::OPS),
+					_ => None,
+				},
+			});
+			alternatives.insert(group.best, scored.collect());
+		}
+	}
+
+	for (idx, entry) in entries.into_iter().enumerate() {
+		let path = paths[idx].as_path();
+		let result = match entry {
+			// Grouped with the other failures like a single file would be
+			BatchEntry::Failed(e) if args.only_errors => {
+				failures.push((path, e));
+				continue;
+			},
+			BatchEntry::Failed(e) if e.kind == CambiaErrorKind::NotRipLog => {
+				skipped.push(path);
+				continue;
+			},
+			BatchEntry::Parsed(_) if args.best_per_album && !alternatives.contains_key(&idx) => continue,
+			BatchEntry::Parsed(mut parsed) => {
+				if !args.only_errors {
+					merge_sibling_cue(&mut siblings, path, &mut parsed);
+					attached_accurips.extend(merge_sibling_accurip(&mut siblings, path, &mut parsed));
+				}
+				let alternatives = alternatives.remove(&idx).unwrap_or_default();
+				DirEntryResult { path, response: Some(parsed), error: None, duplicate_of: None, alternatives }
+			},
+			BatchEntry::Failed(e) => DirEntryResult { path, response: None, error: Some(e), duplicate_of: None, alternatives: Vec::new() },
+			BatchEntry::Duplicate(first) => DirEntryResult { path, response: None, error: None, duplicate_of: Some(paths[first].as_path()), alternatives: Vec::new() },
+			BatchEntry::Cancelled => {
+				cancelled += 1;
+				continue;
+			},
+		};
+
+		if let (Some(deductions), Some(response)) = (deductions.as_mut(), result.response.as_ref()) {
+			write_deductions(deductions, path, response);
+		}
+
+		if !args.only_errors {
+			print_dir_entry(&result, args.pretty);
+		}
+	}
+
+	if let Some(deductions) = deductions.as_mut() {
+		finish_deductions(deductions);
+	}
+
+	// Reports attached to a reported log are left out, including those next to logs that failed or were dropped
+	if !args.only_errors {
+		accurips.iter()
+			.filter(|accurip| !attached_accurips.contains(*accurip))
+			.for_each(|accurip| print_accurip(accurip, args.pretty));
+	}
+
+	if args.only_errors {
+		print_error_groups(failures, args.pretty);
+	}
+
+	if !skipped.is_empty() && args.pretty {
+		println!("Skipped {} files that are not rip logs:", skipped.len());
+		skipped.iter().for_each(|path| println!("  {}", path.display()));
+	} else if !skipped.is_empty() {
+		println!("{}", serde_json::to_string(&SkippedFiles { skipped: "not a rip log", paths: skipped }).unwrap());
+	}
+
+	if cancel.is_cancelled() {
+		tracing::warn!("Scan interrupted, {} queued logs were not parsed", cancelled);
+		std::process::exit(130);
+	}
+}
+
+// One line per distinct failure, ordered by kind so every log from the same unsupported ripper ends up together
+fn print_error_groups<'a>(failures: impl IntoIterator<Item = (&'a Path, CambiaError)>, pretty: bool) {
+	let mut groups: BTreeMap<(CambiaErrorKind, String), Vec<FailedLog>> = BTreeMap::new();
+
+	for (path, e) in failures {
+		groups.entry((e.kind, e.message)).or_default().push(FailedLog { path, offset: e.offset });
+	}
+
+	for ((kind, message), logs) in groups {
+		if !pretty {
+			println!("{}", serde_json::to_string(&ErrorGroup { kind, message, logs }).unwrap());
+			continue;
+		}
+
+		println!("{}: {} ({} logs)", kind, message, logs.len());
+		for log in logs {
+			match log.offset {
+				Some(offset) => println!("  {} (byte {})", log.path.display(), offset),
+				None => println!("  {}", log.path.display()),
+			}
+		}
+	}
+}
+
+fn print_dir_entry(result: &DirEntryResult, pretty: bool) {
+	if !pretty {
+		println!("{}", serde_json::to_string(result).unwrap());
+		return;
+	}
+
+	println!("==> {}", result.path.display());
+	if let Some(response) = result.response.as_ref() {
+		print!("{}", render_text(response));
+	}
+	if let Some(e) = result.error.as_ref() {
+		println!("Error ({}): {}", e.kind, e.message);
+	}
+	if let Some(first) = result.duplicate_of {
+		println!("Duplicate of {}", first.display());
 	}
+	for alternative in result.alternatives.iter() {
+		match alternative.score {
+			Some(score) => println!("Alternative: {} (OPS {})", alternative.path.display(), score),
+			None => println!("Alternative: {}", alternative.path.display()),
+		}
+	}
+	println!();
+}
+
+// Cue sheets and CUETools reports by directory, so each directory is listed once however many logs it holds
+#[derive(Default)]
+struct SiblingIndex {
+	dirs: HashMap<PathBuf, Vec<PathBuf>>,
+}
+
+impl SiblingIndex {
+	// Prefers a file sharing the log's name, otherwise the only file with that extension next to it
+	fn find(&mut self, log_path: &Path, extension: &str) -> Option<PathBuf> {
+		let dir = log_path.parent()?;
+		let files = self.dirs.entry(dir.to_path_buf()).or_insert_with(|| list_sibling_files(dir));
+
+		let same_name = log_path.with_extension(extension);
+		if files.contains(&same_name) {
+			return Some(same_name);
+		}
+
+		let mut matching = files.iter().filter(|p| has_extension(p, extension));
+		match (matching.next(), matching.next()) {
+			(Some(sibling), None) => Some(sibling.clone()),
+			_ => None,
+		}
+	}
+}
+
+fn list_sibling_files(dir: &Path) -> Vec<PathBuf> {
+	let Ok(entries) = std::fs::read_dir(dir) else {
+		return Vec::new();
+	};
+
+	entries.filter_map(|e| e.ok())
+		.map(|e| e.path())
+		.filter(|p| (has_extension(p, "cue") || has_extension(p, "accurip")) && p.is_file())
+		.collect()
+}
+
+fn has_extension(path: &Path, extension: &str) -> bool {
+	path.extension().and_then(std::ffi::OsStr::to_str).is_some_and(|ext| ext.eq_ignore_ascii_case(extension))
+}
+
+fn merge_sibling_cue(siblings: &mut SiblingIndex, log_path: &Path, response: &mut CambiaResponse) {
+	let Some(cue_path) = siblings.find(log_path, "cue") else {
+		return;
+	};
+
+	match std::fs::read(&cue_path) {
+		Ok(cue_raw) => response.metadata.merge(ReleaseMetadata::from_cue_bytes(&cue_raw)),
+		Err(e) => tracing::error!("Error reading {}: {}", cue_path.display(), e),
+	}
+}
+
+// Returns the path of the report when one was attached
+fn merge_sibling_accurip(siblings: &mut SiblingIndex, log_path: &Path, response: &mut CambiaResponse) -> Option<PathBuf> {
+	let accurip_path = siblings.find(log_path, "accurip")?;
+
+	match std::fs::read(&accurip_path) {
+		Ok(accurip_raw) => {
+			response.accurip = Some(AccuripReport::from_bytes(&accurip_raw));
+			Some(accurip_path)
+		},
+		Err(e) => {
+			tracing::error!("Error reading {}: {}", accurip_path.display(), e);
+			None
+		},
+	}
+}
+
+fn print_accurip(path: &Path, pretty: bool) {
+	match std::fs::read(path) {
+		Ok(raw) if pretty => println!("==> {}\n{}", path.display(), render_accurip(&AccuripReport::from_bytes(&raw))),
+		Ok(raw) => println!("{}", serde_json::to_string(&AccuripEntry { path, accurip: AccuripReport::from_bytes(&raw) }).unwrap()),
+		Err(e) => tracing::error!("Error reading {}: {}", path.display(), e),
+	}
+}
+
+fn open_deductions_csv(args: &Args) -> Option<DeductionsCsv> {
+	let csv_path = args.deductions_csv.as_ref()?;
+	match DeductionsCsv::create(csv_path) {
+		Ok(deductions) => Some(deductions),
+		Err(e) => {
+			tracing::error!("Error creating {}: {}", csv_path.display(), e);
+			None
+		},
+	}
+}
+
+fn write_deductions(deductions: &mut DeductionsCsv, path: &Path, response: &CambiaResponse) {
+	if let Err(e) = deductions.write_response(path, response) {
+		tracing::error!("Error writing deductions for {}: {}", path.display(), e);
+	}
+}
+
+fn finish_deductions(deductions: &mut DeductionsCsv) {
+	if let Err(e) = deductions.flush() {
+		tracing::error!("Error writing deductions: {}", e);
+	}
+}
+
+// The first Ctrl-C lets the scan wind down and report what it has, a second one exits immediately
+fn cancel_on_ctrl_c() -> CancellationToken {
+	let cancel = CancellationToken::new();
+	let token = cancel.clone();
+
+	tokio::spawn(async move {
+		if tokio::signal::ctrl_c().await.is_ok() {
+			tracing::warn!("Interrupt received, finishing logs in progress (press Ctrl-C again to exit)");
+			token.cancel();
+		}
+		if tokio::signal::ctrl_c().await.is_ok() {
+			std::process::exit(130);
+		}
+	});
+
+	cancel
+}
+
+pub fn is_log_file(path: &Path) -> bool {
+	path.extension()
+		.and_then(std::ffi::OsStr::to_str)
+		.is_some_and(|ext| ext.eq_ignore_ascii_case("log"))
+}
+
+pub fn parse_since(s: &str) -> Result<SystemTime, String> {
+	if let Ok(duration) = humantime::parse_duration(s) {
+		return SystemTime::now().checked_sub(duration).ok_or_else(|| format!("`{s}` reaches too far back"));
+	}
+
+	// A bare date means the start of that day
+	let timestamp = if s.len() == 10 { format!("{s} 00:00:00") } else { s.to_owned() };
+	humantime::parse_rfc3339_weak(&timestamp)
+		.map_err(|_| format!("`{s}` isn't a duration or a timestamp"))
+}
+
+// Files under a directory in name order, optionally only those modified since a point in time.
+// Directories are read concurrently, on network shares the scan is bound by metadata latency rather than CPU.
+pub fn scan_files(root_path: &Path, threads: usize, since: Option<SystemTime>) -> impl Iterator<Item = PathBuf> {
+	WalkDir::new(root_path)
+		.skip_hidden(false)
+		.sort(true)
+		.parallelism(scan_parallelism(threads))
+		.process_read_dir(move |_, _, _, children| {
+			// Stat calls for --since happen here so they're spread across the scan threads as well
+			if let Some(since) = since {
+				children.retain(|child| child.as_ref().map_or(true, |e| !e.file_type().is_file() || modified_since(e, since)));
+			}
+		})
+		.into_iter()
+		.filter_map(|e| e.ok())
+		.filter(|e| e.file_type().is_file())
+		.map(|e| e.path())
+}
+
+fn scan_parallelism(threads: usize) -> Parallelism {
+	match threads {
+		0 | 1 => Parallelism::Serial,
+		threads => Parallelism::RayonNewPool(threads),
+	}
+}
+
+// Files whose modification time can't be read are kept rather than silently dropped
+fn modified_since(entry: &DirEntry<((), ())>, since: SystemTime) -> bool {
+	entry.metadata().ok()
+		.and_then(|metadata| metadata.modified().ok())
+		.is_none_or(|modified| modified >= since)
+}
+
+fn is_accurip_file(path: &Path) -> bool {
+	has_extension(path, "accurip")
 }
 
 pub fn save_rip_log(root_path: PathBuf, id: &[u8], log_raw: &[u8]) {
@@ -107,9 +107,40 @@ export async function getRipInfoMpMulti(from: string | null, files: FileList | u
     ws.onerror = () => {
         const error: CambiaError = {
             id: [],
-            message: "Connection to the API failed."
+            message: "Connection to the API failed.",
+            kind: "Io",
+            offset: null
         };
         errorStore.set(error);
         goto(`${removeRoute(location.pathname, get(page).route.id)}/error`);
     }
-}
\ No newline at end of file
+}
+
+const BATCH_FETCH_CONCURRENCY = 8;
+
+export async function getBatchFiles(from: string | null): Promise<FileList> {
+    const endpoint = dev ? "http://localhost:3031" : `${location.origin}${removeRoute(location.pathname, from)}`;
+    const listing = await fetch(`${endpoint}/api/v1/batch`);
+    if (!listing.ok) {
+        throw new Error("Batch browsing is not enabled on this server.");
+    }
+
+    const paths = await listing.json() as string[];
+    const files: (File | null)[] = new Array(paths.length).fill(null);
+    // A fixed pool of workers pulls paths off a shared cursor so a large listing doesn't open thousands of requests at once
+    let next = 0;
+    const worker = async () => {
+        while (next < paths.length) {
+            const idx = next++;
+            const res = await fetch(`${endpoint}/api/v1/batch/log?path=${encodeURIComponent(paths[idx])}`);
+            files[idx] = res.ok ? new File([await res.blob()], paths[idx], {type: 'text/plain'}) : null;
+        }
+    };
+    await Promise.all(Array.from({length: Math.min(BATCH_FETCH_CONCURRENCY, paths.length)}, worker));
+
+    const dt = new DataTransfer();
+    files.forEach(file => {
+        if (file) dt.items.add(file);
+    });
+    return dt.files;
+}
@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccuripConfidence } from "./AccuripConfidence";
+import type { AccuripTrack } from "./AccuripTrack";
+
+export interface AccuripReport { version: string | null, ctdb_tocid: string | null, ctdb: AccuripConfidence | null, accuraterip_id: string | null, tracks: Array<AccuripTrack>, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccuripConfidence } from "./AccuripConfidence";
+
+export interface AccuripTrack { num: number, accuraterip: AccuripConfidence | null, ctdb: AccuripConfidence | null, }
\ No newline at end of file
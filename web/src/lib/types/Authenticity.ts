@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AuthenticitySignal } from "./AuthenticitySignal";
+
+export interface Authenticity { likely_virtual: boolean, signals: Array<AuthenticitySignal>, }
\ No newline at end of file
@@ -1,3 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { CambiaErrorKind } from "./CambiaErrorKind";
 
-export interface CambiaError { id: Array<number>, message: string, }
\ No newline at end of file
+export interface CambiaError { id: Array<number>, message: string, kind: CambiaErrorKind, offset: number | null, }
\ No newline at end of file
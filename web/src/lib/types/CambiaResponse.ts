@@ -1,5 +1,10 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { AccuripReport } from "./AccuripReport";
+import type { Authenticity } from "./Authenticity";
+import type { Diagnostics } from "./Diagnostics";
 import type { EvaluationCombined } from "./EvaluationCombined";
+import type { IdHashAlgorithm } from "./IdHashAlgorithm";
 import type { ParsedLogCombined } from "./ParsedLogCombined";
+import type { ReleaseMetadata } from "./ReleaseMetadata";
 
-export interface CambiaResponse { id: Array<number>, parsed: ParsedLogCombined, evaluation_combined: Array<EvaluationCombined>, }
\ No newline at end of file
+export interface CambiaResponse { id: Array<number>, id_hash: IdHashAlgorithm | null, parsed: ParsedLogCombined, metadata: ReleaseMetadata, authenticity: Array<Authenticity>, accurip: AccuripReport | null, evaluation_combined: Array<EvaluationCombined>, diagnostics: Diagnostics | null, }
\ No newline at end of file
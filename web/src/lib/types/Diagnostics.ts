@@ -0,0 +1,5 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { EvaluatorType } from "./EvaluatorType";
+import type { Ripper } from "./Ripper";
+
+export interface Diagnostics { duration_ms: number, encoding: string, log_size: number, parser: Ripper, evaluators: Array<EvaluatorType>, }
\ No newline at end of file
@@ -1,4 +1,4 @@
 // This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
 import type { EvaluationUnitData } from "./EvaluationUnitData";
 
-export interface EvaluationUnit { unit_score: string, data: EvaluationUnitData, }
\ No newline at end of file
+export interface EvaluationUnit { unit_score: string, data: EvaluationUnitData, applied: boolean, }
\ No newline at end of file
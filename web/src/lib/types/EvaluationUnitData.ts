@@ -3,4 +3,4 @@ import type { EvaluationUnitClass } from "./EvaluationUnitClass";
 import type { EvaluationUnitField } from "./EvaluationUnitField";
 import type { EvaluationUnitScope } from "./EvaluationUnitScope";
 
-export interface EvaluationUnitData { scope: EvaluationUnitScope, field: EvaluationUnitField, message: string, class: EvaluationUnitClass, }
\ No newline at end of file
+export interface EvaluationUnitData { code: string, scope: EvaluationUnitScope, field: EvaluationUnitField, message: string, class: EvaluationUnitClass, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { Quartet } from "./Quartet";
+
+export interface ExternalCompressor { executable: string, encoder: string, options: string, lossy: boolean, verify: Quartet, strips_tags: boolean, }
\ No newline at end of file
@@ -0,0 +1,4 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+import type { TrackMetadata } from "./TrackMetadata";
+
+export interface ReleaseMetadata { performer: string | null, title: string | null, tracks: Array<TrackMetadata>, }
\ No newline at end of file
@@ -0,0 +1,3 @@
+// This file was generated by [ts-rs](https://github.com/Aleph-Alpha/ts-rs). Do not edit this file manually.
+
+export interface TrackMetadata { num: number, performer: string | null, title: string | null, isrc: string | null, }
\ No newline at end of file
@@ -124,7 +124,9 @@ export function getInfoOverviewPopoverText(miniName: string) {
 export function clientError(message: string, id: Array<number> = []): MessageEvent {
 	const err: CambiaError = {
 		id,
-		message
+		message,
+		kind: 'Other',
+		offset: null
 	};
 	const packed = packr.pack(err);
 	return new MessageEvent('message', { data: new Blob([packed]) });